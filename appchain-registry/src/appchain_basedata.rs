@@ -1,9 +1,14 @@
 use crate::{
-    types::{AppchainMetadata, AppchainState, AppchainStatus},
+    types::{
+        AnchorDeploymentStatus, AnchorTeardownStatus, AnchorUpgrade, AnchorUpgradeStatus,
+        AppchainHealth, AppchainMetadata, AppchainRegistrationRecord, AppchainRejection,
+        AppchainState, AppchainStatus, AuditReport, RegisterDepositAsset,
+        RegisterDepositRefundCase,
+    },
     *,
 };
 use core::convert::TryInto;
-use near_sdk::{collections::LazyOption, json_types::U64, Timestamp};
+use near_sdk::{collections::LazyOption, json_types::U64, StorageUsage, Timestamp};
 
 /// Appchain basedata
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -14,6 +19,7 @@ pub struct AppchainBasedata {
     pub appchain_anchor: Option<AccountId>,
     pub appchain_owner: AccountId,
     pub register_deposit: Balance,
+    pub register_deposit_asset: RegisterDepositAsset,
     pub appchain_state: AppchainState,
     pub upvote_deposit: Balance,
     pub downvote_deposit: Balance,
@@ -22,6 +28,20 @@ pub struct AppchainBasedata {
     pub validator_count: u32,
     pub total_stake: Balance,
     pub dao_proposal_url: Option<String>,
+    pub voting_deadline: Option<Timestamp>,
+    pub register_deposit_refund_case: Option<RegisterDepositRefundCase>,
+    pub refundable_register_deposit: Option<Balance>,
+    pub refunded_register_deposit: Option<Balance>,
+    pub storage_usage: StorageUsage,
+    pub audit_report: Option<AuditReport>,
+    pub rejection: Option<AppchainRejection>,
+    pub previous_registrations: Vec<AppchainRegistrationRecord>,
+    pub anchor_deployment_status: Option<AnchorDeploymentStatus>,
+    pub anchor_template_version: Option<String>,
+    pub anchor_upgrade: Option<AnchorUpgrade>,
+    pub anchor_teardown_status: Option<AnchorTeardownStatus>,
+    pub last_sync_time: Option<Timestamp>,
+    pub pending_vote_deposit_refunds: u32,
 }
 
 impl AppchainBasedata {
@@ -32,6 +52,7 @@ impl AppchainBasedata {
         appchain_metadata: AppchainMetadata,
         appchain_owner: AccountId,
         register_deposit: Balance,
+        register_deposit_asset: RegisterDepositAsset,
     ) -> Self {
         Self {
             appchain_id: appchain_id.clone(),
@@ -43,6 +64,7 @@ impl AppchainBasedata {
             appchain_anchor: None,
             appchain_owner,
             register_deposit,
+            register_deposit_asset,
             appchain_state: AppchainState::Registered,
             upvote_deposit: 0,
             downvote_deposit: 0,
@@ -51,6 +73,20 @@ impl AppchainBasedata {
             validator_count: 0,
             total_stake: 0,
             dao_proposal_url: None,
+            voting_deadline: None,
+            register_deposit_refund_case: None,
+            refundable_register_deposit: None,
+            refunded_register_deposit: None,
+            storage_usage: 0,
+            audit_report: None,
+            rejection: None,
+            previous_registrations: Vec::new(),
+            anchor_deployment_status: None,
+            anchor_template_version: None,
+            anchor_upgrade: None,
+            anchor_teardown_status: None,
+            last_sync_time: None,
+            pending_vote_deposit_refunds: 0,
         }
     }
     /// Get appchain id
@@ -73,6 +109,10 @@ impl AppchainBasedata {
     pub fn register_deposit(&self) -> Balance {
         self.register_deposit
     }
+    /// Get the asset which paid the register deposit
+    pub fn register_deposit_asset(&self) -> RegisterDepositAsset {
+        self.register_deposit_asset.clone()
+    }
     /// Get state
     pub fn state(&self) -> AppchainState {
         self.appchain_state.clone()
@@ -98,8 +138,26 @@ impl AppchainBasedata {
             0
         }
     }
+    /// Get the health of the appchain with the given stale threshold (in seconds).
+    ///
+    /// Only active appchains are checked, and `None` is returned if the threshold is 0.
+    /// If the anchor has never synced the appchain, the go live time is used instead.
+    pub fn health(&self, stale_threshold_in_seconds: u64) -> Option<AppchainHealth> {
+        if stale_threshold_in_seconds == 0 || self.appchain_state != AppchainState::Active {
+            return None;
+        }
+        let last_seen_time = self.last_sync_time.unwrap_or(self.go_live_time);
+        if env::block_timestamp()
+            > last_seen_time
+                .saturating_add(stale_threshold_in_seconds.saturating_mul(NANO_SECONDS_MULTIPLE))
+        {
+            Some(AppchainHealth::Stale)
+        } else {
+            Some(AppchainHealth::Healthy)
+        }
+    }
     /// Get full status
-    pub fn status(&self) -> AppchainStatus {
+    pub fn status(&self, stale_threshold_in_seconds: u64) -> AppchainStatus {
         AppchainStatus {
             appchain_id: self.appchain_id.clone(),
             evm_chain_id: self.evm_chain_id,
@@ -107,6 +165,7 @@ impl AppchainBasedata {
             appchain_anchor: self.appchain_anchor.clone(),
             appchain_owner: self.appchain_owner.clone(),
             register_deposit: self.register_deposit.into(),
+            register_deposit_asset: self.register_deposit_asset.clone(),
             appchain_state: self.appchain_state.clone(),
             upvote_deposit: self.upvote_deposit.into(),
             downvote_deposit: self.downvote_deposit.into(),
@@ -116,6 +175,21 @@ impl AppchainBasedata {
             validator_count: self.validator_count,
             total_stake: self.total_stake.into(),
             dao_proposal_url: self.dao_proposal_url.clone(),
+            voting_deadline: self.voting_deadline.map(|deadline| deadline.into()),
+            register_deposit_refund_case: self.register_deposit_refund_case.clone(),
+            refundable_register_deposit: self
+                .refundable_register_deposit
+                .map(|amount| amount.into()),
+            refunded_register_deposit: self.refunded_register_deposit.map(|amount| amount.into()),
+            audit_report: self.audit_report.clone(),
+            rejection: self.rejection.clone(),
+            previous_registrations: self.previous_registrations.clone(),
+            anchor_deployment_status: self.anchor_deployment_status.clone(),
+            anchor_template_version: self.anchor_template_version.clone(),
+            anchor_upgrade: self.anchor_upgrade.clone(),
+            anchor_teardown_status: self.anchor_teardown_status.clone(),
+            last_sync_time: self.last_sync_time.map(|time| time.into()),
+            health: self.health(stale_threshold_in_seconds),
         }
     }
     /// Change owner
@@ -131,6 +205,46 @@ impl AppchainBasedata {
     pub fn set_metadata(&mut self, metadata: AppchainMetadata) {
         self.appchain_metadata.set(&metadata);
     }
+    /// Archive the current registration, and reset the registration data
+    /// (including voting data) with the given metadata and register deposit.
+    ///
+    /// The state of appchain should be changed separately.
+    pub fn reset_for_reapplying(
+        &mut self,
+        evm_chain_id: Option<U64>,
+        metadata: AppchainMetadata,
+        register_deposit: Balance,
+        register_deposit_asset: RegisterDepositAsset,
+    ) {
+        self.previous_registrations
+            .push(AppchainRegistrationRecord {
+                registered_time: self.registered_time.into(),
+                reapplied_time: env::block_timestamp().into(),
+                register_deposit: self.register_deposit.into(),
+                register_deposit_asset: self.register_deposit_asset.clone(),
+                refunded_register_deposit: self
+                    .refunded_register_deposit
+                    .map(|amount| amount.into()),
+                dao_proposal_url: self.dao_proposal_url.clone(),
+                audit_report: self.audit_report.clone(),
+                rejection: self.rejection.clone(),
+            });
+        self.evm_chain_id = evm_chain_id;
+        self.appchain_metadata.set(&metadata);
+        self.register_deposit = register_deposit;
+        self.register_deposit_asset = register_deposit_asset;
+        self.registered_time = env::block_timestamp();
+        self.dao_proposal_url = None;
+        self.voting_deadline = None;
+        self.register_deposit_refund_case = None;
+        self.refundable_register_deposit = None;
+        self.refunded_register_deposit = None;
+        self.audit_report = None;
+        self.rejection = None;
+        env::storage_remove(
+            &StorageKey::AppchainVotingScore(self.appchain_id.clone()).into_bytes(),
+        );
+    }
     /// Set initial deposit
     pub fn set_initial_deposit(&mut self, deposit: Balance) {
         self.register_deposit = deposit;
@@ -139,6 +253,38 @@ impl AppchainBasedata {
     pub fn set_anchor_account(&mut self, anchor_account: AccountId) {
         self.appchain_anchor = Some(anchor_account);
     }
+    /// Set the status of the deployment of anchor contract code
+    pub fn set_anchor_deployment_status(&mut self, status: AnchorDeploymentStatus) {
+        self.anchor_deployment_status = Some(status);
+    }
+    /// Set the version of the anchor template deployed to anchor account
+    pub fn set_anchor_template_version(&mut self, version: String) {
+        self.anchor_template_version = Some(version);
+    }
+    /// Start upgrading the anchor contract to the given template version
+    pub fn start_anchor_upgrade(&mut self, to_version: String) {
+        self.anchor_upgrade = Some(AnchorUpgrade {
+            from_version: self.anchor_template_version.clone(),
+            to_version,
+            status: AnchorUpgradeStatus::Upgrading,
+            start_time: env::block_timestamp().into(),
+        });
+    }
+    /// Set the status of the teardown of the anchor account
+    pub fn set_anchor_teardown_status(&mut self, status: AnchorTeardownStatus) {
+        self.anchor_teardown_status = Some(status);
+    }
+    /// Set the result of the upgrade of the anchor contract
+    pub fn finish_anchor_upgrade(&mut self, succeeded: bool) {
+        if let Some(anchor_upgrade) = self.anchor_upgrade.as_mut() {
+            if succeeded {
+                anchor_upgrade.status = AnchorUpgradeStatus::Upgraded;
+                self.anchor_template_version = Some(anchor_upgrade.to_version.clone());
+            } else {
+                anchor_upgrade.status = AnchorUpgradeStatus::Failed;
+            }
+        }
+    }
     /// Set total stake
     pub fn set_total_stake(&mut self, total_stake: Balance) {
         self.total_stake = total_stake;
@@ -147,18 +293,40 @@ impl AppchainBasedata {
     pub fn set_state(&mut self, new_state: AppchainState) {
         if self.appchain_state.eq(&AppchainState::Booting) && new_state.eq(&AppchainState::Active) {
             self.go_live_time = env::block_timestamp();
+            self.register_deposit_refund_case = Some(RegisterDepositRefundCase::WentLive);
         }
         self.appchain_state = new_state;
     }
-    /// Sync staking status
+    /// Set the case in which the register deposit can be refunded
+    pub fn set_register_deposit_refund_case(&mut self, case: RegisterDepositRefundCase) {
+        self.register_deposit_refund_case = Some(case);
+    }
+    /// Set the amount of register deposit which can be refunded to the owner
+    pub fn set_refundable_register_deposit(&mut self, amount: Balance) {
+        self.refundable_register_deposit = Some(amount);
+    }
+    /// Set the amount of refunded register deposit
+    pub fn set_refunded_register_deposit(&mut self, amount: Option<Balance>) {
+        self.refunded_register_deposit = amount;
+    }
+    /// Sync staking status, and record the time of the sync
     pub fn sync_staking_status(&mut self, validator_count: u32, total_stake: Balance) {
         self.validator_count = validator_count;
         self.total_stake = total_stake;
+        self.last_sync_time = Some(env::block_timestamp());
     }
     /// Increase upvote deposit
     pub fn increase_upvote_deposit(&mut self, value: Balance) {
         self.upvote_deposit += value;
     }
+    /// Increase the count of vote deposit refunds (or withdrawals) which are in flight
+    pub fn increase_pending_vote_deposit_refunds(&mut self) {
+        self.pending_vote_deposit_refunds += 1;
+    }
+    /// Decrease the count of vote deposit refunds (or withdrawals) which are in flight
+    pub fn decrease_pending_vote_deposit_refunds(&mut self) {
+        self.pending_vote_deposit_refunds = self.pending_vote_deposit_refunds.saturating_sub(1);
+    }
     /// Decrease upvote deposit
     pub fn decrease_upvote_deposit(&mut self, value: Balance) {
         self.upvote_deposit = self
@@ -15,6 +15,7 @@ extern crate std;
 mod appchain_basedata;
 mod preclude;
 mod storage_key;
+mod storage_management;
 pub mod storage_migration;
 pub mod types;
 mod upgrade;
@@ -24,11 +25,11 @@ mod registry_viewer;
 use crate::preclude::*;
 use appchain_basedata::AppchainBasedata;
 use core::convert::TryFrom;
-use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::{
     assert_self,
     borsh::{self, maybestd::collections::HashMap, BorshDeserialize, BorshSerialize},
-    collections::{LazyOption, LookupMap, UnorderedSet},
+    collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet, Vector},
     env, ext_contract,
     json_types::{U128, U64},
     log, near_bindgen,
@@ -37,8 +38,14 @@ use near_sdk::{
     PromiseResult, PublicKey, Timestamp,
 };
 use storage_key::StorageKey;
+use storage_management::AccountStorageBalance;
 use types::{
-    AppchainId, AppchainMetadata, AppchainState, AppchainType, RegistryRoles, RegistrySettings,
+    AnchorDeploymentStatus, AnchorFunctionCall, AnchorFunctionCallRecord, AnchorFunctionCallStatus,
+    AnchorTeardownStatus, AnchorTemplate, AppchainId, AppchainLiveStatus, AppchainMetadata,
+    AppchainRegistrationArgs, AppchainState, AppchainStateActor, AppchainStateChange, AppchainType,
+    ArchivedAppchain, AuditAttestation, MultiTxsOperationProcessingResult, RegisterDepositAsset,
+    RegistryRoles, RegistrySettings, RegistryTreasury, VersionedAnchorSyncPayload,
+    VotingScoreCountingStatus,
 };
 
 const VERSION: &str = "v4.0.0";
@@ -49,6 +56,10 @@ const IBC_ANCHOR_INIT_BALANCE: Balance = 5_000_000_000_000_000_000_000_000;
 const T_GAS_FOR_RESOLVER_FUNCTION: u64 = 10;
 const T_GAS_FOR_FT_TRANSFER: u64 = 20;
 const T_GAS_FOR_CALLING_ANCHOR_FUNCTION: u64 = 150;
+const T_GAS_FOR_ANCHOR_INITIALIZATION: u64 = 50;
+const T_GAS_FOR_STORING_ANCHOR_CODE: u64 = 30;
+const T_GAS_FOR_UPDATING_ANCHOR: u64 = 60;
+const T_GAS_FOR_ANCHOR_TEARDOWN: u64 = 100;
 const OCT_DECIMALS_BASE: u128 = 1000_000_000_000_000_000;
 /// Default register deposit amount
 const DEFAULT_REGISTER_DEPOSIT: u128 = 1000;
@@ -56,6 +67,26 @@ const DEFAULT_REGISTER_DEPOSIT: u128 = 1000;
 const NANO_SECONDS_MULTIPLE: u64 = 1_000_000_000;
 /// Default staging duration of contract code for upgrade
 const DEFAULT_CONTRACT_CODE_STAGING_DURATION: u64 = 3600 * 24;
+/// Default interval (in seconds) between two rounds of counting voting score
+const DEFAULT_VOTING_SCORE_COUNTING_INTERVAL: u64 = 3600 * 24;
+/// Default percent of voting score decay in each counting round
+const DEFAULT_VOTING_SCORE_DECAY_PERCENT: u16 = 0;
+/// Default duration (in seconds) of the voting period of an appchain
+const DEFAULT_VOTING_DURATION: u64 = 3600 * 24 * 30;
+/// Default stale threshold (in seconds) of the syncs from appchain anchors.
+const DEFAULT_ANCHOR_SYNC_STALE_THRESHOLD: u64 = 3600 * 24 * 2;
+/// The gas cap for processing an operation which may be processed in multiple transactions
+const T_GAS_CAP_FOR_MULTI_TXS_PROCESSING: u64 = 130;
+/// The gas for refunding the vote deposits of a voter, including the resolver
+const T_GAS_FOR_REFUNDING_VOTE_DEPOSITS_OF_A_VOTER: u64 = 40;
+/// The gas reserved for finishing the current function after scheduling a call to appchain anchor
+const T_GAS_FOR_SCHEDULING_ANCHOR_FUNCTION_CALL: u64 = 5;
+/// The max number of the records of function calls to appchain anchors kept in registry
+const MAX_COUNT_OF_ANCHOR_FUNCTION_CALL_RECORDS: u64 = 1000;
+/// The max bytes of the return value kept in the record of a function call to appchain anchor
+const MAX_BYTES_OF_ANCHOR_FUNCTION_RETURN_VALUE: usize = 512;
+/// The bytes of storage for the storage balance record of an account
+const STORAGE_BALANCE_RECORD_BYTES: u64 = 128;
 
 const APPCHAIN_NOT_FOUND: &'static str = "Appchain not found.";
 
@@ -75,6 +106,52 @@ pub trait SelfCallback {
         account_id: AccountId,
         amount: U128,
     );
+    /// Resolver for refunding the vote deposits of a voter
+    fn resolve_vote_deposits_refund(
+        &mut self,
+        appchain_id: AppchainId,
+        account_id: AccountId,
+        upvote_deposit: U128,
+        downvote_deposit: U128,
+    );
+    /// Resolver for refunding the register deposit of an appchain
+    fn resolve_register_deposit_refund(
+        &mut self,
+        appchain_id: AppchainId,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    );
+    /// Resolver for claiming the register deposit refunds of removed appchains
+    fn resolve_claimable_register_deposit_refund(
+        &mut self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    );
+    /// Resolver for claiming the vote deposits of removed appchains
+    fn resolve_claimable_vote_deposit_refund(&mut self, account_id: AccountId, amount: U128);
+    /// Resolver for sweeping the fees to treasury account
+    fn resolve_treasury_sweep(
+        &mut self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    );
+    /// Resolver for deploying and initializing the anchor contract of an appchain
+    fn resolve_anchor_deployment(&mut self, appchain_id: AppchainId);
+    /// Resolver for upgrading the anchor contract of an appchain,
+    /// the storage cost is refunded to the caller if the upgrade fails
+    fn resolve_anchor_upgrade(
+        &mut self,
+        appchain_id: AppchainId,
+        caller: AccountId,
+        storage_cost: U128,
+    );
+    /// Resolver for tearing down the anchor account of an appchain which is going to be removed
+    fn resolve_anchor_teardown(&mut self, appchain_id: AppchainId);
+    /// Resolver for calling a function of the anchor of an appchain
+    fn resolve_anchor_function_call(&mut self, call_id: U64);
 }
 
 /// The callback interface for appchain anchor
@@ -87,6 +164,8 @@ pub trait AppchainAnchorCallback {
         validator_count: u32,
         total_stake: U128,
     );
+    /// Sync the status of an appchain to registry, with the versioned payload
+    fn sync_status_of(&mut self, appchain_id: AppchainId, payload: VersionedAnchorSyncPayload);
 }
 
 #[near_bindgen]
@@ -112,33 +191,57 @@ pub struct AppchainRegistry {
     upvote_deposits: LookupMap<(AppchainId, AccountId), Balance>,
     /// The map from pair (appchain id, account id) to their downvote deposit
     downvote_deposits: LookupMap<(AppchainId, AccountId), Balance>,
+    /// The map from appchain id to the set of accounts which have vote deposit for it
+    appchain_voters: LookupMap<AppchainId, UnorderedSet<AccountId>>,
+    /// The map from account id to the set of appchains which the account has vote deposit for
+    voted_appchains: LookupMap<AccountId, UnorderedSet<AppchainId>>,
     /// The total stake of OCT token in all appchains
     total_stake: Balance,
     /// The roles of appchain registry
     registry_roles: LazyOption<RegistryRoles>,
     /// Whether the asset transfer is paused
     asset_transfer_is_paused: bool,
+    /// The status of counting voting score of appchains
+    voting_score_counting_status: VotingScoreCountingStatus,
+    /// The ledger of register deposits collected by appchain registry
+    registry_treasury: RegistryTreasury,
+    /// The storage balances of accounts which register appchains
+    storage_balances: LookupMap<AccountId, AccountStorageBalance>,
+    /// The map from appchain id to the history of its state changes
+    appchain_state_histories: LookupMap<AppchainId, Vector<AppchainStateChange>>,
+    /// The map from appchain id to the attestations of auditors on it
+    appchain_audit_attestations: LookupMap<AppchainId, Vec<AuditAttestation>>,
+    /// The summaries of appchains which are removed from registry,
+    /// indexed by pair (appchain id, removal index)
+    archived_appchains: UnorderedMap<(AppchainId, u32), ArchivedAppchain>,
+    /// The map from template key to the versions of anchor contract code
+    anchor_templates: UnorderedMap<String, AnchorTemplate>,
+    /// The records of function calls to appchain anchors, indexed by call id
+    anchor_function_calls: LookupMap<u64, AnchorFunctionCallRecord>,
+    /// The range of call ids (start inclusive, end exclusive) of the kept records
+    /// of function calls to appchain anchors
+    anchor_function_call_id_range: (u64, u64),
+    /// The map from the method name of anchor which can be called by octopus council
+    /// to the appchain types it is allowed for (`None` for all appchain types)
+    allowed_anchor_methods: UnorderedMap<String, Option<Vec<AppchainType>>>,
+    /// The map from appchain id to its live status reported by its anchor
+    appchain_live_statuses: LookupMap<AppchainId, AppchainLiveStatus>,
+    /// The map from pair (account id, asset) to the unclaimed register deposit refunds
+    /// of the removed appchains owned by the account
+    claimable_register_deposits: LookupMap<(AccountId, RegisterDepositAsset), Balance>,
+    /// The map from account id to the vote deposits (in OCT token) of the removed appchains
+    /// which failed to be withdrawn or refunded to the account
+    claimable_vote_deposits: LookupMap<AccountId, Balance>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
+#[allow(clippy::enum_variant_names)]
 enum RegistryDepositMessage {
-    RegisterAppchain {
-        appchain_id: String,
-        description: String,
-        appchain_type: AppchainType,
-        evm_chain_id: Option<U64>,
-        website_url: String,
-        github_address: String,
-        contact_email: String,
-        premined_wrapped_appchain_token_beneficiary: AccountId,
-        premined_wrapped_appchain_token: U128,
-        initial_supply_of_wrapped_appchain_token: U128,
-        ido_amount_of_wrapped_appchain_token: U128,
-        initial_era_reward: U128,
-        fungible_token_metadata: FungibleTokenMetadata,
-        custom_metadata: HashMap<String, String>,
-    },
+    RegisterAppchain(AppchainRegistrationArgs),
+    ReapplyAppchain(AppchainRegistrationArgs),
+    UpvoteAppchain { appchain_id: String },
+    DownvoteAppchain { appchain_id: String },
 }
 
 #[near_bindgen]
@@ -161,12 +264,35 @@ impl AppchainRegistry {
             appchain_basedatas: LookupMap::new(StorageKey::AppchainBasedatas.into_bytes()),
             upvote_deposits: LookupMap::new(StorageKey::UpvoteDeposits.into_bytes()),
             downvote_deposits: LookupMap::new(StorageKey::DownvoteDeposits.into_bytes()),
+            appchain_voters: LookupMap::new(StorageKey::AppchainVoters.into_bytes()),
+            voted_appchains: LookupMap::new(StorageKey::VotedAppchains.into_bytes()),
             total_stake: 0,
             registry_roles: LazyOption::new(
                 StorageKey::RegistryRoles.into_bytes(),
                 Some(&RegistryRoles::default()),
             ),
             asset_transfer_is_paused: false,
+            voting_score_counting_status: VotingScoreCountingStatus::default(),
+            registry_treasury: RegistryTreasury::default(),
+            storage_balances: LookupMap::new(StorageKey::StorageBalances.into_bytes()),
+            appchain_state_histories: LookupMap::new(
+                StorageKey::AppchainStateHistories.into_bytes(),
+            ),
+            appchain_audit_attestations: LookupMap::new(
+                StorageKey::AppchainAuditAttestations.into_bytes(),
+            ),
+            archived_appchains: UnorderedMap::new(StorageKey::ArchivedAppchains.into_bytes()),
+            anchor_templates: UnorderedMap::new(StorageKey::AnchorTemplates.into_bytes()),
+            anchor_function_calls: LookupMap::new(StorageKey::AnchorFunctionCalls.into_bytes()),
+            anchor_function_call_id_range: (0, 0),
+            allowed_anchor_methods: UnorderedMap::new(
+                StorageKey::AllowedAnchorMethods.into_bytes(),
+            ),
+            appchain_live_statuses: LookupMap::new(StorageKey::AppchainLiveStatuses.into_bytes()),
+            claimable_register_deposits: LookupMap::new(
+                StorageKey::ClaimableRegisterDeposits.into_bytes(),
+            ),
+            claimable_vote_deposits: LookupMap::new(StorageKey::ClaimableVoteDeposits.into_bytes()),
         }
     }
     // Assert the asset transfer is not paused.
@@ -202,6 +328,16 @@ impl AppchainRegistry {
             "Function can only be called by registry settings manager."
         );
     }
+    // Assert that the contract is called by one of the auditors.
+    fn assert_auditor(&self) {
+        let registry_roles = self.registry_roles.get().unwrap();
+        assert!(
+            registry_roles
+                .auditors
+                .contains(&env::predecessor_account_id()),
+            "Function can only be called by auditors."
+        );
+    }
     //
     fn assert_octopus_council(&self) {
         let registry_roles = self.registry_roles.get().unwrap();
@@ -213,6 +349,20 @@ impl AppchainRegistry {
             "Only octopus council account can call this function."
         );
     }
+    // Assert that the contract is called by the owner or octopus council.
+    fn assert_owner_or_octopus_council(&self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller.eq(&self.owner)
+                || self
+                    .registry_roles
+                    .get()
+                    .unwrap()
+                    .octopus_council
+                    .is_some_and(|council| council.eq(&caller)),
+            "Function can only be called by owner or octopus council."
+        );
+    }
     // Assert that the given account has no role in this contract.
     fn assert_account_has_no_role(&self, account: &AccountId) {
         let registry_roles = self.registry_roles.get().unwrap();
@@ -239,6 +389,58 @@ impl AppchainRegistry {
             appchain_basedata.state(),
         );
     }
+    // Change the state of an appchain, the transition must be allowed in the transition table.
+    fn change_appchain_state(
+        &mut self,
+        appchain_basedata: &mut AppchainBasedata,
+        new_state: AppchainState,
+        actor: AppchainStateActor,
+        reason: Option<String>,
+    ) {
+        assert!(
+            appchain_basedata.state().can_transit_to(&new_state, &actor),
+            "Appchain state can NOT be changed from '{}' to '{}' by {:?}.",
+            appchain_basedata.state(),
+            new_state,
+            actor
+        );
+        self.internal_set_appchain_state(appchain_basedata, new_state, reason, false);
+    }
+    // Set the state of an appchain and record the change in its state history.
+    fn internal_set_appchain_state(
+        &mut self,
+        appchain_basedata: &mut AppchainBasedata,
+        new_state: AppchainState,
+        reason: Option<String>,
+        forced: bool,
+    ) {
+        let appchain_id = appchain_basedata.id().clone();
+        let mut history = self
+            .appchain_state_histories
+            .get(&appchain_id)
+            .unwrap_or_else(|| {
+                Vector::new(StorageKey::AppchainStateHistoryOf(appchain_id.clone()).into_bytes())
+            });
+        history.push(&AppchainStateChange {
+            from: appchain_basedata.state(),
+            to: new_state.clone(),
+            timestamp: env::block_timestamp().into(),
+            block_height: env::block_height().into(),
+            actor: env::predecessor_account_id(),
+            reason,
+            forced,
+        });
+        self.appchain_state_histories.insert(&appchain_id, &history);
+        appchain_basedata.set_state(new_state);
+    }
+    // Assert that the deposit received in `ft_on_transfer` is OCT token.
+    fn assert_oct_token_deposit(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.oct_token,
+            "Only OCT token can be deposited for this action."
+        );
+    }
     // Get AppchainBasedata from storage
     fn get_appchain_basedata(&self, appchain_id: &AppchainId) -> AppchainBasedata {
         self.appchain_basedatas
@@ -263,24 +465,90 @@ impl AppchainRegistry {
             &sender_id,
             msg
         );
-        assert_eq!(
-            &env::predecessor_account_id(),
-            &self.oct_token,
-            "Invalid deposit '{}' of unknown NEP-141 asset from '{}' received. Return deposit.",
-            amount.0,
-            sender_id,
-        );
+        let asset = RegisterDepositAsset::FungibleToken(env::predecessor_account_id());
+        if !env::predecessor_account_id().eq(&self.oct_token)
+            && self
+                .registry_settings
+                .get()
+                .unwrap()
+                .minimum_register_deposit_of(&asset, &self.oct_token)
+                .is_none()
+        {
+            log!(
+                "Invalid deposit '{}' of unknown NEP-141 asset from '{}' received. Return deposit.",
+                amount.0,
+                sender_id,
+            );
+            return PromiseOrValue::Value(amount);
+        }
 
-        let parse_result = serde_json::from_str(msg.as_str());
-        assert!(
-            parse_result.is_ok(),
-            "Invalid msg '{}' attached in `ft_transfer_call`. Refund deposit.",
-            msg
-        );
-        let deposit_message = parse_result.unwrap();
+        let deposit_message = match serde_json::from_str(msg.as_str()) {
+            Ok(deposit_message) => deposit_message,
+            Err(_) => {
+                log!(
+                    "Invalid msg '{}' attached in `ft_transfer_call`. Return deposit.",
+                    msg
+                );
+                return PromiseOrValue::Value(amount);
+            }
+        };
 
         match deposit_message {
-            RegistryDepositMessage::RegisterAppchain {
+            RegistryDepositMessage::RegisterAppchain(registration_args) => {
+                match self.internal_register_appchain(sender_id, registration_args, asset, amount.0)
+                {
+                    Ok(excess) => PromiseOrValue::Value(excess.into()),
+                    Err(message) => {
+                        log!("{} Return deposit.", message);
+                        PromiseOrValue::Value(amount)
+                    }
+                }
+            }
+            RegistryDepositMessage::ReapplyAppchain(registration_args) => {
+                match self.internal_reapply_appchain(sender_id, registration_args, asset, amount.0)
+                {
+                    Ok(excess) => PromiseOrValue::Value(excess.into()),
+                    Err(message) => {
+                        log!("{} Return deposit.", message);
+                        PromiseOrValue::Value(amount)
+                    }
+                }
+            }
+            RegistryDepositMessage::UpvoteAppchain { appchain_id } => {
+                self.assert_oct_token_deposit();
+                self.internal_upvote_appchain(appchain_id, sender_id, amount.0)
+            }
+            RegistryDepositMessage::DownvoteAppchain { appchain_id } => {
+                self.assert_oct_token_deposit();
+                self.internal_downvote_appchain(appchain_id, sender_id, amount.0)
+            }
+        }
+    }
+    /// Register an appchain with native NEAR token as register deposit
+    #[payable]
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        description: String,
+        appchain_type: AppchainType,
+        evm_chain_id: Option<U64>,
+        website_url: String,
+        github_address: String,
+        contact_email: String,
+        premined_wrapped_appchain_token_beneficiary: AccountId,
+        premined_wrapped_appchain_token: U128,
+        initial_supply_of_wrapped_appchain_token: U128,
+        ido_amount_of_wrapped_appchain_token: U128,
+        initial_era_reward: U128,
+        fungible_token_metadata: FungibleTokenMetadata,
+        custom_metadata: HashMap<String, String>,
+    ) {
+        self.assert_asset_transfer_is_not_paused();
+        let sender_id = env::predecessor_account_id();
+        match self.internal_register_appchain(
+            sender_id.clone(),
+            AppchainRegistrationArgs {
                 appchain_id,
                 description,
                 appchain_type,
@@ -295,38 +563,27 @@ impl AppchainRegistry {
                 initial_era_reward,
                 fungible_token_metadata,
                 custom_metadata,
-            } => {
-                self.register_appchain(
-                    sender_id,
-                    appchain_id,
-                    description,
-                    appchain_type,
-                    evm_chain_id,
-                    amount.0,
-                    website_url,
-                    github_address,
-                    contact_email,
-                    premined_wrapped_appchain_token_beneficiary,
-                    premined_wrapped_appchain_token,
-                    initial_supply_of_wrapped_appchain_token,
-                    ido_amount_of_wrapped_appchain_token,
-                    initial_era_reward,
-                    fungible_token_metadata,
-                    custom_metadata,
-                );
-                PromiseOrValue::Value(0.into())
+            },
+            RegisterDepositAsset::Near,
+            env::attached_deposit(),
+        ) {
+            Ok(excess) => {
+                if excess > 0 {
+                    Promise::new(sender_id).transfer(excess);
+                }
             }
+            Err(message) => env::panic_str(message.as_str()),
         }
     }
-    //
-    fn register_appchain(
+    /// Reapply a closed appchain with native NEAR token as register deposit
+    #[payable]
+    #[allow(clippy::too_many_arguments)]
+    pub fn reapply_appchain(
         &mut self,
-        sender_id: AccountId,
         appchain_id: AppchainId,
         description: String,
         appchain_type: AppchainType,
         evm_chain_id: Option<U64>,
-        register_deposit: Balance,
         website_url: String,
         github_address: String,
         contact_email: String,
@@ -338,76 +595,19 @@ impl AppchainRegistry {
         fungible_token_metadata: FungibleTokenMetadata,
         custom_metadata: HashMap<String, String>,
     ) {
-        assert_ne!(
-            sender_id, self.owner,
-            "The register account should NOT be the contract owner."
-        );
-        assert!(
-            self.appchain_basedatas.get(&appchain_id).is_none(),
-            "Appchain already registered."
-        );
-        assert!(
-            register_deposit.eq(&self
-                .registry_settings
-                .get()
-                .unwrap()
-                .minimum_register_deposit
-                .0),
-            "Invalid register deposit."
-        );
-        assert!(
-            !appchain_id.trim().is_empty(),
-            "Missing necessary field 'appchain_id'."
-        );
-        assert!(appchain_id.find(".").is_none(), "Invalid 'appchain_id'.");
-        assert!(
-            appchain_id.len() <= 20,
-            "Appchain id is too long (max length is 20)."
-        );
-        assert!(
-            AccountId::try_from(format!("{}.{}", appchain_id, env::current_account_id())).is_ok(),
-            "Invalid 'appchain_id'."
-        );
-        assert!(
-            !website_url.trim().is_empty(),
-            "Missing necessary field 'website_url'."
-        );
-        assert!(
-            !github_address.trim().is_empty(),
-            "Missing necessary field 'github_address'."
-        );
-        assert!(
-            !contact_email.trim().is_empty(),
-            "Missing necessary field 'contact_email'."
-        );
-        fungible_token_metadata.assert_valid();
-        assert!(
-            !fungible_token_metadata.name.trim().is_empty(),
-            "Missing necessary field 'fungible token name'."
-        );
-        assert!(
-            !fungible_token_metadata.symbol.trim().is_empty(),
-            "Missing necessary field 'fungible token symbol'."
-        );
-        assert!(
-            initial_supply_of_wrapped_appchain_token.0 >= premined_wrapped_appchain_token.0,
-            "The initial supply of wrapped appchain token should not be less than the premined amount."
-        );
-        //
-        let appchain_basedata = AppchainBasedata::new(
-            appchain_id.clone(),
-            evm_chain_id,
-            AppchainMetadata {
+        self.assert_asset_transfer_is_not_paused();
+        let sender_id = env::predecessor_account_id();
+        match self.internal_reapply_appchain(
+            sender_id.clone(),
+            AppchainRegistrationArgs {
+                appchain_id,
                 description,
                 appchain_type,
+                evm_chain_id,
                 website_url,
-                function_spec_url: String::new(),
                 github_address,
-                github_release: String::new(),
                 contact_email,
-                premined_wrapped_appchain_token_beneficiary: Some(
-                    premined_wrapped_appchain_token_beneficiary,
-                ),
+                premined_wrapped_appchain_token_beneficiary,
                 premined_wrapped_appchain_token,
                 initial_supply_of_wrapped_appchain_token,
                 ido_amount_of_wrapped_appchain_token,
@@ -415,39 +615,379 @@ impl AppchainRegistry {
                 fungible_token_metadata,
                 custom_metadata,
             },
-            sender_id,
+            RegisterDepositAsset::Near,
+            env::attached_deposit(),
+        ) {
+            Ok(excess) => {
+                if excess > 0 {
+                    Promise::new(sender_id).transfer(excess);
+                }
+            }
+            Err(message) => env::panic_str(message.as_str()),
+        }
+    }
+    /// Register an appchain with the given register deposit.
+    ///
+    /// Return the amount of deposit exceeding the minimum register deposit,
+    /// or the reason why the registration is refused.
+    fn internal_register_appchain(
+        &mut self,
+        sender_id: AccountId,
+        registration_args: AppchainRegistrationArgs,
+        register_deposit_asset: RegisterDepositAsset,
+        register_deposit: Balance,
+    ) -> Result<Balance, String> {
+        let (appchain_id, evm_chain_id, appchain_metadata) = registration_args.into_parts();
+        if sender_id.eq(&self.owner) {
+            return Err("The register account should NOT be the contract owner.".to_string());
+        }
+        if self.appchain_basedatas.get(&appchain_id).is_some() {
+            return Err("Appchain already registered.".to_string());
+        }
+        if appchain_id.trim().is_empty() {
+            return Err("Missing necessary field 'appchain_id'.".to_string());
+        }
+        if appchain_id.contains('.')
+            || AccountId::try_from(format!("{}.{}", appchain_id, env::current_account_id()))
+                .is_err()
+        {
+            return Err("Invalid 'appchain_id'.".to_string());
+        }
+        if appchain_id.len() > 20 {
+            return Err("Appchain id is too long (max length is 20).".to_string());
+        }
+        let minimum_register_deposit = self.internal_check_registration(
+            &appchain_metadata,
+            &register_deposit_asset,
             register_deposit,
+        )?;
+        //
+        let storage_usage_before = env::storage_usage();
+        let mut appchain_basedata = AppchainBasedata::new(
+            appchain_id.clone(),
+            evm_chain_id,
+            appchain_metadata,
+            sender_id,
+            minimum_register_deposit,
+            register_deposit_asset.clone(),
         );
         self.appchain_ids.insert(&appchain_id);
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
+        let storage_usage = env::storage_usage() - storage_usage_before;
+        if let Err(message) =
+            self.internal_charge_storage_usage(&appchain_basedata.owner(), storage_usage)
+        {
+            self.appchain_ids.remove(&appchain_id);
+            self.appchain_basedatas.remove(&appchain_id);
+            env::storage_remove(&StorageKey::AppchainMetadata(appchain_id.clone()).into_bytes());
+            return Err(message);
+        }
+        appchain_basedata.storage_usage = storage_usage;
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        self.internal_accept_register_deposit(&register_deposit_asset, minimum_register_deposit);
         log!(
             "Appchain '{}' is registered by '{}'.",
             appchain_basedata.id(),
             appchain_basedata.owner()
         );
+        Ok(register_deposit - minimum_register_deposit)
     }
-    //
+    /// Reapply a closed appchain by its owner, with a new register deposit and updated metadata.
+    ///
+    /// The previous registration is archived in the appchain, and the state history is kept.
+    /// Return the amount of deposit exceeding the minimum register deposit,
+    /// or the reason why the reapplication is refused.
+    fn internal_reapply_appchain(
+        &mut self,
+        sender_id: AccountId,
+        registration_args: AppchainRegistrationArgs,
+        register_deposit_asset: RegisterDepositAsset,
+        register_deposit: Balance,
+    ) -> Result<Balance, String> {
+        let (appchain_id, evm_chain_id, appchain_metadata) = registration_args.into_parts();
+        let mut appchain_basedata = match self.appchain_basedatas.get(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => return Err(APPCHAIN_NOT_FOUND.to_string()),
+        };
+        if !appchain_basedata.owner().eq(&sender_id) {
+            return Err("Only the appchain owner can reapply the appchain.".to_string());
+        }
+        if !appchain_basedata.state().eq(&AppchainState::Closed) {
+            return Err("Only a closed appchain can be reapplied.".to_string());
+        }
+        if appchain_basedata.anchor().is_some() {
+            return Err("An appchain which has started booting can NOT be reapplied.".to_string());
+        }
+        if appchain_basedata.upvote_deposit() > 0 || appchain_basedata.downvote_deposit() > 0 {
+            return Err("The vote deposits of the appchain should be refunded first.".to_string());
+        }
+        if appchain_basedata.pending_vote_deposit_refunds > 0 {
+            return Err(
+                "The vote deposit refunds (or withdrawals) of the appchain are in flight, try again later."
+                    .to_string(),
+            );
+        }
+        if appchain_basedata.refundable_register_deposit.unwrap_or(0) > 0
+            && appchain_basedata.refunded_register_deposit.is_none()
+        {
+            return Err(
+                "The refund of the previous register deposit should be claimed first.".to_string(),
+            );
+        }
+        // The urls set by appchain lifecycle manager are kept.
+        let previous_metadata = appchain_basedata.metadata();
+        let appchain_metadata = AppchainMetadata {
+            function_spec_url: previous_metadata.function_spec_url,
+            github_release: previous_metadata.github_release,
+            ..appchain_metadata
+        };
+        let minimum_register_deposit = self.internal_check_registration(
+            &appchain_metadata,
+            &register_deposit_asset,
+            register_deposit,
+        )?;
+        // Keep the data changed by the reapplication, for reverting the changes
+        // if the storage balance of the owner is NOT enough.
+        let appchain_basedata_before = self.get_appchain_basedata(&appchain_id);
+        let metadata_before = appchain_basedata_before.metadata();
+        let voting_score_key = StorageKey::AppchainVotingScore(appchain_id.clone()).into_bytes();
+        let voting_score_before = env::storage_read(&voting_score_key);
+        let audit_attestations_before = self.appchain_audit_attestations.get(&appchain_id);
+        //
+        let storage_usage_before = env::storage_usage();
+        appchain_basedata.reset_for_reapplying(
+            evm_chain_id,
+            appchain_metadata,
+            minimum_register_deposit,
+            register_deposit_asset.clone(),
+        );
+        self.change_appchain_state(
+            &mut appchain_basedata,
+            AppchainState::Registered,
+            AppchainStateActor::AppchainOwner,
+            Some("Reapplied by appchain owner.".to_string()),
+        );
+        self.appchain_audit_attestations.remove(&appchain_id);
+        if let Err(message) = self.internal_save_appchain_with_storage_charged(
+            &mut appchain_basedata,
+            storage_usage_before,
+        ) {
+            let mut appchain_basedata = appchain_basedata_before;
+            appchain_basedata.set_metadata(metadata_before);
+            self.appchain_basedatas
+                .insert(&appchain_id, &appchain_basedata);
+            if let Some(voting_score) = voting_score_before {
+                env::storage_write(&voting_score_key, &voting_score);
+            }
+            if let Some(audit_attestations) = audit_attestations_before {
+                self.appchain_audit_attestations
+                    .insert(&appchain_id, &audit_attestations);
+            }
+            if let Some(mut history) = self.appchain_state_histories.get(&appchain_id) {
+                history.pop();
+                self.appchain_state_histories.insert(&appchain_id, &history);
+            }
+            return Err(message);
+        }
+        self.internal_accept_register_deposit(&register_deposit_asset, minimum_register_deposit);
+        log!(
+            "Appchain '{}' is reapplied by '{}'.",
+            appchain_basedata.id(),
+            appchain_basedata.owner()
+        );
+        Ok(register_deposit - minimum_register_deposit)
+    }
+    /// Check the metadata and the register deposit of a registration (or reapplication).
+    ///
+    /// Return the minimum register deposit of the asset, which is kept by the registry.
+    fn internal_check_registration(
+        &self,
+        appchain_metadata: &AppchainMetadata,
+        register_deposit_asset: &RegisterDepositAsset,
+        register_deposit: Balance,
+    ) -> Result<Balance, String> {
+        let minimum_register_deposit = match self
+            .registry_settings
+            .get()
+            .unwrap()
+            .minimum_register_deposit_of(register_deposit_asset, &self.oct_token)
+        {
+            Some(amount) => amount,
+            None => return Err("The asset is not accepted for register deposit.".to_string()),
+        };
+        if register_deposit < minimum_register_deposit {
+            return Err(format!(
+                "Insufficient register deposit. The minimum is '{}'.",
+                minimum_register_deposit
+            ));
+        }
+        appchain_metadata.validate()?;
+        Ok(minimum_register_deposit)
+    }
+    /// Keep the minimum register deposit of a registration (or reapplication)
+    /// as pending register deposit in treasury.
+    fn internal_accept_register_deposit(
+        &mut self,
+        register_deposit_asset: &RegisterDepositAsset,
+        minimum_register_deposit: Balance,
+    ) {
+        let ledger = self.registry_treasury.ledger_of_mut(register_deposit_asset);
+        ledger.pending_register_deposits =
+            (ledger.pending_register_deposits.0 + minimum_register_deposit).into();
+    }
+    /// Call a function of the anchor of an appchain, with the attached deposit.
+    /// Return the id of the call, for querying the result of it.
+    #[payable]
     pub fn call_anchor_function(
         &mut self,
         appchain_id: String,
         function_name: String,
         args: String,
-    ) {
+        gas: Option<U64>,
+    ) -> U64 {
+        self.assert_octopus_council();
+        self.internal_call_anchor_function(AnchorFunctionCall {
+            appchain_id,
+            function_name,
+            args,
+            deposit: env::attached_deposit().into(),
+            gas,
+        })
+    }
+    /// Call functions of the anchors of appchains.
+    /// The attached deposit should be equal to the total deposit of the function calls.
+    /// Return the ids of the calls, for querying the results of them.
+    #[payable]
+    pub fn batch_call_anchor_functions(&mut self, calls: Vec<AnchorFunctionCall>) -> Vec<U64> {
         self.assert_octopus_council();
+        assert!(!calls.is_empty(), "No function call is specified.");
+        let total_deposit: Balance = calls.iter().map(|call| call.deposit.0).sum();
+        assert_eq!(
+            env::attached_deposit(),
+            total_deposit,
+            "The attached deposit should be equal to the total deposit of the function calls."
+        );
+        let total_gas: u64 = calls
+            .iter()
+            .map(|call| {
+                call.gas
+                    .map_or(Gas::ONE_TERA.0 * T_GAS_FOR_CALLING_ANCHOR_FUNCTION, |gas| {
+                        gas.0
+                    })
+                    .saturating_add(
+                        Gas::ONE_TERA.0
+                            * (T_GAS_FOR_RESOLVER_FUNCTION
+                                + T_GAS_FOR_SCHEDULING_ANCHOR_FUNCTION_CALL),
+                    )
+            })
+            .fold(0, u64::saturating_add);
+        assert!(
+            total_gas <= env::prepaid_gas().0.saturating_sub(env::used_gas().0),
+            "Insufficient gas for the function calls. The total gas needed is '{}'.",
+            total_gas
+        );
+        calls
+            .into_iter()
+            .map(|call| self.internal_call_anchor_function(call))
+            .collect()
+    }
+    //
+    fn internal_call_anchor_function(&mut self, call: AnchorFunctionCall) -> U64 {
         self.assert_appchain_state(
-            &appchain_id,
+            &call.appchain_id,
             [AppchainState::Booting, AppchainState::Active].to_vec(),
         );
+        let appchain_type = self
+            .get_appchain_basedata(&call.appchain_id)
+            .metadata()
+            .appchain_type;
+        let method_is_allowed = match self.allowed_anchor_methods.get(&call.function_name) {
+            Some(Some(appchain_types)) => appchain_types.contains(&appchain_type),
+            Some(None) => true,
+            None => false,
+        };
+        assert!(
+            method_is_allowed,
+            "Method '{}' of anchor is not allowed for appchain '{}'.",
+            call.function_name, call.appchain_id
+        );
         //
-        let anchor_account_id =
-            AccountId::try_from(format!("{}.{}", &appchain_id, env::current_account_id())).unwrap();
-        Promise::new(anchor_account_id).function_call(
-            function_name,
-            args.into_bytes(),
-            0,
-            Gas::ONE_TERA * T_GAS_FOR_CALLING_ANCHOR_FUNCTION,
+        let anchor_account_id = AccountId::try_from(format!(
+            "{}.{}",
+            &call.appchain_id,
+            env::current_account_id()
+        ))
+        .unwrap();
+        let (start_id, end_id) = self.anchor_function_call_id_range;
+        let call_id = U64::from(end_id);
+        self.anchor_function_calls.insert(
+            &end_id,
+            &AnchorFunctionCallRecord {
+                call_id,
+                appchain_id: call.appchain_id.clone(),
+                function_name: call.function_name.clone(),
+                caller: env::predecessor_account_id(),
+                deposit: call.deposit,
+                status: AnchorFunctionCallStatus::Pending,
+                return_value: None,
+                called_at: env::block_timestamp().into(),
+            },
         );
+        self.anchor_function_call_id_range = (
+            self.internal_prune_anchor_function_calls(start_id, end_id + 1),
+            end_id + 1,
+        );
+        // The gas of the call is clamped to leave enough gas for the resolver.
+        let available_gas = env::prepaid_gas().0.saturating_sub(
+            env::used_gas().0
+                + Gas::ONE_TERA.0
+                    * (T_GAS_FOR_RESOLVER_FUNCTION + T_GAS_FOR_SCHEDULING_ANCHOR_FUNCTION_CALL),
+        );
+        let gas = core::cmp::min(
+            call.gas
+                .map_or(Gas::ONE_TERA.0 * T_GAS_FOR_CALLING_ANCHOR_FUNCTION, |gas| {
+                    gas.0
+                }),
+            available_gas,
+        );
+        assert!(gas > 0, "Insufficient gas for the function call.");
+        Promise::new(anchor_account_id)
+            .function_call(
+                call.function_name,
+                call.args.into_bytes(),
+                call.deposit.0,
+                Gas(gas),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA * T_GAS_FOR_RESOLVER_FUNCTION)
+                    .with_unused_gas_weight(0)
+                    .resolve_anchor_function_call(call_id),
+            );
+        call_id
+    }
+    /// Remove the earliest records of function calls to appchain anchors, if the count of
+    /// the records exceeds the limit. The pending records are kept for their resolvers.
+    ///
+    /// At most 2 records are removed at a time, to keep the gas usage bounded.
+    /// Return the new start id of the kept records.
+    fn internal_prune_anchor_function_calls(&mut self, start_id: u64, end_id: u64) -> u64 {
+        let mut start_id = start_id;
+        for _ in 0..2 {
+            if end_id - start_id <= MAX_COUNT_OF_ANCHOR_FUNCTION_CALL_RECORDS {
+                break;
+            }
+            match self.anchor_function_calls.get(&start_id) {
+                Some(record) if record.status == AnchorFunctionCallStatus::Pending => break,
+                _ => {
+                    self.anchor_function_calls.remove(&start_id);
+                    start_id += 1;
+                }
+            }
+        }
+        start_id
     }
 }
 
@@ -466,11 +1006,61 @@ impl AppchainRegistry {
 }
 
 impl AppchainRegistry {
+    /// Keep the summary of an appchain which is going to be removed.
+    fn internal_archive_appchain(&mut self, appchain_basedata: &AppchainBasedata) {
+        let closed_time = self
+            .appchain_state_histories
+            .get(appchain_basedata.id())
+            .and_then(|history| history.get(history.len().checked_sub(1)?))
+            .map_or(0, |state_change| state_change.timestamp.0);
+        // The id of a removed appchain can be registered again, so it may be archived before.
+        let mut removal_index = 0;
+        while self
+            .archived_appchains
+            .get(&(appchain_basedata.id().clone(), removal_index))
+            .is_some()
+        {
+            removal_index += 1;
+        }
+        self.archived_appchains.insert(
+            &(appchain_basedata.id().clone(), removal_index),
+            &ArchivedAppchain {
+                appchain_id: appchain_basedata.id().clone(),
+                removal_index,
+                appchain_owner: appchain_basedata.owner(),
+                appchain_type: appchain_basedata.metadata().appchain_type,
+                appchain_anchor: appchain_basedata.anchor(),
+                final_state: appchain_basedata.state(),
+                registered_time: appchain_basedata.registered_time.into(),
+                go_live_time: appchain_basedata.go_live_time.into(),
+                closed_time: closed_time.into(),
+                removed_time: env::block_timestamp().into(),
+            },
+        );
+    }
     ///
     fn internal_remove_appchain(&mut self, appchain_id: &AppchainId) {
+        self.internal_release_register_deposit_of(appchain_id);
+        if let Some(appchain_basedata) = self.appchain_basedatas.get(appchain_id) {
+            self.internal_release_storage_usage(
+                &appchain_basedata.owner(),
+                appchain_basedata.storage_usage,
+            );
+            self.internal_archive_appchain(&appchain_basedata);
+        }
         env::storage_remove(&StorageKey::AppchainAnchorCode(appchain_id.clone()).into_bytes());
         env::storage_remove(&StorageKey::AppchainMetadata(appchain_id.clone()).into_bytes());
         env::storage_remove(&StorageKey::AppchainVotingScore(appchain_id.clone()).into_bytes());
+        self.appchain_audit_attestations.remove(appchain_id);
+        self.appchain_live_statuses.remove(appchain_id);
+        if let Some(mut history) = self.appchain_state_histories.remove(appchain_id) {
+            history.clear();
+        }
+        assert!(
+            self.appchain_voters.get(appchain_id).is_none(),
+            "The vote deposits of appchain '{}' are NOT refunded.",
+            appchain_id
+        );
         self.appchain_ids.remove(&appchain_id);
         self.appchain_basedatas.remove(&appchain_id);
     }
@@ -486,7 +1076,44 @@ impl AppchainAnchorCallback for AppchainRegistry {
         validator_count: u32,
         total_stake: U128,
     ) {
-        let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        self.internal_sync_state_of(&appchain_id, appchain_state, validator_count, total_stake);
+    }
+    //
+    fn sync_status_of(&mut self, appchain_id: AppchainId, payload: VersionedAnchorSyncPayload) {
+        match payload {
+            VersionedAnchorSyncPayload::V1(payload) => {
+                self.internal_sync_state_of(
+                    &appchain_id,
+                    payload.appchain_state,
+                    payload.validator_count,
+                    payload.total_stake,
+                );
+                self.appchain_live_statuses.insert(
+                    &appchain_id,
+                    &AppchainLiveStatus {
+                        era_number: payload.era_number,
+                        last_era_reward: payload.last_era_reward,
+                        total_bridged_token_supply: payload.total_bridged_token_supply,
+                        anchor_version: payload.anchor_version,
+                        appchain_block_height: payload.appchain_block_height,
+                        updated_at: env::block_timestamp().into(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl AppchainRegistry {
+    //
+    fn internal_sync_state_of(
+        &mut self,
+        appchain_id: &AppchainId,
+        appchain_state: AppchainState,
+        validator_count: u32,
+        total_stake: U128,
+    ) {
+        let mut appchain_basedata = self.get_appchain_basedata(appchain_id);
         assert!(
             appchain_basedata.anchor().is_some(),
             "Anchor of appchain {} is not set.",
@@ -500,12 +1127,21 @@ impl AppchainAnchorCallback for AppchainRegistry {
             "Only appchain anchor can call this function."
         );
         assert!(
-            appchain_state.is_managed_by_anchor(),
-            "Invalid state to sync."
+            appchain_basedata.state().is_managed_by_anchor(),
+            "The state of appchain '{}' is NOT managed by anchor.",
+            appchain_id
         );
-        appchain_basedata.set_state(appchain_state);
+        if !appchain_basedata.state().eq(&appchain_state) {
+            self.change_appchain_state(
+                &mut appchain_basedata,
+                appchain_state,
+                AppchainStateActor::AppchainAnchor,
+                None,
+            );
+        }
+        self.internal_settle_register_deposit(&mut appchain_basedata);
         appchain_basedata.sync_staking_status(validator_count, total_stake.0);
         self.appchain_basedatas
-            .insert(&appchain_id, &appchain_basedata);
+            .insert(appchain_id, &appchain_basedata);
     }
 }
@@ -1,5 +1,10 @@
 use crate::{
-    types::{AppchainSortingField, AppchainState, AppchainStatus, SortingOrder},
+    types::{
+        AccountVote, AllowedAnchorMethod, AnchorFunctionCallRecord, AnchorTemplate, AppchainHealth,
+        AppchainLiveStatus, AppchainSortingField, AppchainState, AppchainStateChange,
+        AppchainStateTransition, AppchainStatus, AppchainVoter, ArchivedAppchain, AuditAttestation,
+        RegistryTreasury, SortingOrder, VotingScoreCountingStatus,
+    },
     *,
 };
 use near_sdk::json_types::U64;
@@ -40,6 +45,78 @@ pub trait RegistryViewer {
     fn get_upvote_deposit_for(&self, appchain_id: AppchainId, account_id: AccountId) -> U128;
     /// Get downvote deposit of a given account id for a certain appchain
     fn get_downvote_deposit_for(&self, appchain_id: AppchainId, account_id: AccountId) -> U128;
+    /// Get the status of counting voting score of appchains
+    fn get_voting_score_counting_status(&self) -> VotingScoreCountingStatus;
+    /// Get voters of an appchain and their vote deposits, starting from the given index
+    ///
+    /// When a voter is removed (by withdrawing or refunding all of its vote deposits),
+    /// the last voter is moved to its position. So the pages may skip or repeat voters
+    /// if the voters change between two calls.
+    fn get_voters_of(
+        &self,
+        appchain_id: AppchainId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AppchainVoter>;
+    /// Get all appchains that an account has vote deposit for, and the vote deposits
+    fn get_votes_of_account(&self, account_id: AccountId) -> Vec<AccountVote>;
+    /// Get the allowed transitions from the current state of an appchain.
+    fn get_allowed_transitions(&self, appchain_id: AppchainId) -> Vec<AppchainStateTransition>;
+    /// Get the history of state changes of an appchain, in the order of occurrence.
+    fn get_appchain_state_history(
+        &self,
+        appchain_id: AppchainId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AppchainStateChange>;
+    /// Get the attestations of auditors on an appchain.
+    fn get_audit_attestations_of(&self, appchain_id: AppchainId) -> Vec<AuditAttestation>;
+    /// Get the summaries of appchains which are removed from registry, starting from the given index
+    fn get_archived_appchains(&self, from_index: u32, limit: u32) -> Vec<ArchivedAppchain>;
+    /// Get the summary of an appchain which is removed from registry.
+    /// The removal index is 0 for the first removal of the appchain id, and so on.
+    fn get_archived_appchain(
+        &self,
+        appchain_id: AppchainId,
+        removal_index: u32,
+    ) -> Option<ArchivedAppchain>;
+    /// Show the ledger of register deposits collected by appchain registry.
+    fn get_registry_treasury(&self) -> RegistryTreasury;
+    /// Get the unclaimed register deposit refunds (in the given asset) of the removed appchains
+    /// which were owned by an account.
+    fn get_claimable_register_deposit_of(
+        &self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+    ) -> U128;
+    /// Get the vote deposits of the removed appchains which failed to be withdrawn
+    /// or refunded to an account.
+    fn get_claimable_vote_deposit_of(&self, account_id: AccountId) -> U128;
+    /// Get all versions of anchor contract code registered in registry.
+    fn get_anchor_templates(&self) -> Vec<AnchorTemplate>;
+    /// Get the anchor template which will be deployed at booting for the given appchain type.
+    fn get_current_anchor_template(&self, appchain_type: AppchainType) -> Option<AnchorTemplate>;
+    /// Get the version of the anchor template which is deployed to the anchor of an appchain.
+    fn get_anchor_template_version_of(&self, appchain_id: AppchainId) -> Option<String>;
+    /// Get the records of function calls to appchain anchors, starting from the given call id.
+    /// Only the latest records are kept, the earlier ones are skipped.
+    fn get_anchor_function_calls(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AnchorFunctionCallRecord>;
+    /// Get the record of a function call to appchain anchor by its call id.
+    fn get_anchor_function_call(&self, call_id: U64) -> Option<AnchorFunctionCallRecord>;
+    /// Get the methods of appchain anchor which can be called by octopus council.
+    fn get_allowed_anchor_methods(&self) -> Vec<AllowedAnchorMethod>;
+    /// Get the live status of an appchain reported by its anchor.
+    fn get_appchain_live_status_of(&self, appchain_id: AppchainId) -> Option<AppchainLiveStatus>;
+    /// Get the status of active appchains whose anchor has NOT synced for longer than
+    /// the stale threshold in registry settings.
+    ///
+    /// Only the appchains in the given range of all appchains are checked, so the result
+    /// may contain less than `limit` appchains even if there are more stale ones.
+    fn get_stale_appchains(&self, from_index: u32, limit: u32) -> Vec<AppchainStatus>;
 }
 
 #[near_bindgen]
@@ -82,7 +159,7 @@ impl RegistryViewer for AppchainRegistry {
             })
             .for_each(|appchain_id| {
                 if let Some(appchain_basedata) = self.appchain_basedatas.get(appchain_id) {
-                    total_stake += appchain_basedata.status().total_stake.0;
+                    total_stake += appchain_basedata.total_stake;
                 }
             });
         U128::from(total_stake)
@@ -102,6 +179,7 @@ impl RegistryViewer for AppchainRegistry {
     ) -> Vec<AppchainStatus> {
         assert!(page_number > 0, "Invalid page number.");
         assert!(page_size >= 5 && page_size <= 50, "Invalid page size.");
+        let stale_threshold = self.anchor_sync_stale_threshold();
         let mut results: Vec<AppchainStatus> = Vec::new();
         for id in self.appchain_ids.to_vec() {
             let appchain_basedata = self.get_appchain_basedata(&id);
@@ -109,12 +187,12 @@ impl RegistryViewer for AppchainRegistry {
                 Some(ref states) => {
                     for state in states {
                         if appchain_basedata.state().eq(state) {
-                            results.push(appchain_basedata.status());
+                            results.push(appchain_basedata.status(stale_threshold));
                             break;
                         }
                     }
                 }
-                None => results.push(appchain_basedata.status()),
+                None => results.push(appchain_basedata.status(stale_threshold)),
             }
         }
         if results.len() > 0 {
@@ -164,7 +242,7 @@ impl RegistryViewer for AppchainRegistry {
     //
     fn get_appchain_status_of(&self, appchain_id: AppchainId) -> AppchainStatus {
         let appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        appchain_basedata.status()
+        appchain_basedata.status(self.anchor_sync_stale_threshold())
     }
     //
     fn get_upvote_deposit_for(&self, appchain_id: AppchainId, account_id: AccountId) -> U128 {
@@ -180,4 +258,197 @@ impl RegistryViewer for AppchainRegistry {
             None => 0.into(),
         }
     }
+    //
+    fn get_voting_score_counting_status(&self) -> VotingScoreCountingStatus {
+        self.voting_score_counting_status.clone()
+    }
+    //
+    fn get_voters_of(
+        &self,
+        appchain_id: AppchainId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AppchainVoter> {
+        assert!(limit > 0 && limit <= 50, "Invalid limit.");
+        let mut results = Vec::<AppchainVoter>::new();
+        if let Some(voters) = self.appchain_voters.get(&appchain_id) {
+            let voters = voters.as_vector();
+            let end_index = core::cmp::min(from_index as u64 + limit as u64, voters.len());
+            for index in from_index as u64..end_index {
+                let voter_id = voters.get(index).unwrap();
+                results.push(AppchainVoter {
+                    upvote_deposit: self
+                        .get_upvote_deposit_for(appchain_id.clone(), voter_id.clone()),
+                    downvote_deposit: self
+                        .get_downvote_deposit_for(appchain_id.clone(), voter_id.clone()),
+                    voter_id,
+                });
+            }
+        }
+        results
+    }
+    //
+    fn get_votes_of_account(&self, account_id: AccountId) -> Vec<AccountVote> {
+        let mut results = Vec::<AccountVote>::new();
+        if let Some(voted_appchains) = self.voted_appchains.get(&account_id) {
+            for appchain_id in voted_appchains.to_vec() {
+                results.push(AccountVote {
+                    upvote_deposit: self
+                        .get_upvote_deposit_for(appchain_id.clone(), account_id.clone()),
+                    downvote_deposit: self
+                        .get_downvote_deposit_for(appchain_id.clone(), account_id.clone()),
+                    appchain_id,
+                });
+            }
+        }
+        results
+    }
+    //
+    fn get_registry_treasury(&self) -> RegistryTreasury {
+        self.registry_treasury.clone()
+    }
+    //
+    fn get_claimable_register_deposit_of(
+        &self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+    ) -> U128 {
+        self.claimable_register_deposits
+            .get(&(account_id, asset))
+            .unwrap_or(0)
+            .into()
+    }
+    //
+    fn get_claimable_vote_deposit_of(&self, account_id: AccountId) -> U128 {
+        self.claimable_vote_deposits
+            .get(&account_id)
+            .unwrap_or(0)
+            .into()
+    }
+    //
+    fn get_allowed_transitions(&self, appchain_id: AppchainId) -> Vec<AppchainStateTransition> {
+        let appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        // An appchain which has started booting can NOT be reapplied.
+        let booted = appchain_basedata.anchor().is_some();
+        appchain_basedata
+            .state()
+            .allowed_transitions()
+            .into_iter()
+            .filter(|transition| {
+                !(booted
+                    && transition.from.eq(&AppchainState::Closed)
+                    && transition.to.eq(&AppchainState::Registered))
+            })
+            .collect()
+    }
+    //
+    fn get_appchain_state_history(
+        &self,
+        appchain_id: AppchainId,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AppchainStateChange> {
+        assert!(limit > 0 && limit <= 50, "Invalid limit.");
+        let mut results = Vec::<AppchainStateChange>::new();
+        if let Some(history) = self.appchain_state_histories.get(&appchain_id) {
+            let end_index = core::cmp::min(from_index as u64 + limit as u64, history.len());
+            for index in from_index as u64..end_index {
+                results.push(history.get(index).unwrap());
+            }
+        }
+        results
+    }
+    //
+    fn get_audit_attestations_of(&self, appchain_id: AppchainId) -> Vec<AuditAttestation> {
+        self.appchain_audit_attestations
+            .get(&appchain_id)
+            .unwrap_or_default()
+    }
+    //
+    fn get_archived_appchains(&self, from_index: u32, limit: u32) -> Vec<ArchivedAppchain> {
+        assert!(limit > 0 && limit <= 50, "Invalid limit.");
+        let archived_appchains = self.archived_appchains.values_as_vector();
+        let end_index = core::cmp::min(from_index as u64 + limit as u64, archived_appchains.len());
+        (from_index as u64..end_index)
+            .map(|index| archived_appchains.get(index).unwrap())
+            .collect()
+    }
+    //
+    fn get_archived_appchain(
+        &self,
+        appchain_id: AppchainId,
+        removal_index: u32,
+    ) -> Option<ArchivedAppchain> {
+        self.archived_appchains.get(&(appchain_id, removal_index))
+    }
+    //
+    fn get_anchor_templates(&self) -> Vec<AnchorTemplate> {
+        self.anchor_templates.values().collect()
+    }
+    //
+    fn get_current_anchor_template(&self, appchain_type: AppchainType) -> Option<AnchorTemplate> {
+        self.current_anchor_template_of(&appchain_type)
+    }
+    //
+    fn get_anchor_template_version_of(&self, appchain_id: AppchainId) -> Option<String> {
+        self.get_appchain_basedata(&appchain_id)
+            .anchor_template_version
+    }
+    //
+    fn get_anchor_function_calls(
+        &self,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<AnchorFunctionCallRecord> {
+        assert!(limit > 0 && limit <= 50, "Invalid limit.");
+        let (start_id, end_id) = self.anchor_function_call_id_range;
+        let from_id = core::cmp::max(from_index as u64, start_id);
+        let end_index = core::cmp::min(from_index as u64 + limit as u64, end_id);
+        (from_id..end_index)
+            .map(|call_id| self.anchor_function_calls.get(&call_id).unwrap())
+            .collect()
+    }
+    //
+    fn get_anchor_function_call(&self, call_id: U64) -> Option<AnchorFunctionCallRecord> {
+        self.anchor_function_calls.get(&call_id.0)
+    }
+    //
+    fn get_allowed_anchor_methods(&self) -> Vec<AllowedAnchorMethod> {
+        self.allowed_anchor_methods
+            .iter()
+            .map(|(method_name, appchain_types)| AllowedAnchorMethod {
+                method_name,
+                appchain_types,
+            })
+            .collect()
+    }
+    //
+    fn get_appchain_live_status_of(&self, appchain_id: AppchainId) -> Option<AppchainLiveStatus> {
+        self.appchain_live_statuses.get(&appchain_id)
+    }
+    //
+    fn get_stale_appchains(&self, from_index: u32, limit: u32) -> Vec<AppchainStatus> {
+        assert!(limit > 0 && limit <= 50, "Invalid limit.");
+        let stale_threshold = self.anchor_sync_stale_threshold();
+        let appchain_ids = self.appchain_ids.as_vector();
+        let end_index = core::cmp::min(from_index as u64 + limit as u64, appchain_ids.len());
+        (from_index as u64..end_index)
+            .map(|index| self.get_appchain_basedata(&appchain_ids.get(index).unwrap()))
+            .filter(|appchain_basedata| {
+                appchain_basedata.health(stale_threshold) == Some(AppchainHealth::Stale)
+            })
+            .map(|appchain_basedata| appchain_basedata.status(stale_threshold))
+            .collect()
+    }
+}
+
+impl AppchainRegistry {
+    //
+    fn anchor_sync_stale_threshold(&self) -> u64 {
+        self.registry_settings
+            .get()
+            .unwrap()
+            .anchor_sync_stale_threshold_in_seconds
+            .0
+    }
 }
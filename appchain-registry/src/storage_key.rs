@@ -9,9 +9,26 @@ pub enum StorageKey {
     RegistrySettings,
     RegistryRoles,
     RegistryContractWasm,
+    AnchorContractWasm,
     AppchainMetadata(AppchainId),
     AppchainAnchorCode(AppchainId),
     AppchainVotingScore(AppchainId),
+    AppchainVoters,
+    AppchainVotersOf(AppchainId),
+    VotedAppchains,
+    VotedAppchainsOf(AccountId),
+    StorageBalances,
+    AppchainStateHistories,
+    AppchainStateHistoryOf(AppchainId),
+    AppchainAuditAttestations,
+    ArchivedAppchains,
+    AnchorTemplates,
+    AnchorTemplateWasm(String),
+    AnchorFunctionCalls,
+    AllowedAnchorMethods,
+    AppchainLiveStatuses,
+    ClaimableRegisterDeposits,
+    ClaimableVoteDeposits,
 }
 
 impl StorageKey {
@@ -24,9 +41,26 @@ impl StorageKey {
             StorageKey::RegistrySettings => "rs".to_string(),
             StorageKey::RegistryRoles => "rr".to_string(),
             StorageKey::RegistryContractWasm => "rcw".to_string(),
+            StorageKey::AnchorContractWasm => "acw".to_string(),
             StorageKey::AppchainMetadata(appchain_id) => format!("{}md", appchain_id),
             StorageKey::AppchainAnchorCode(appchain_id) => format!("{}ac", appchain_id),
             StorageKey::AppchainVotingScore(appchain_id) => format!("{}vs", appchain_id),
+            StorageKey::AppchainVoters => "avs".to_string(),
+            StorageKey::AppchainVotersOf(appchain_id) => format!("{}avs", appchain_id),
+            StorageKey::VotedAppchains => "vas".to_string(),
+            StorageKey::VotedAppchainsOf(account_id) => format!("{}vas", account_id),
+            StorageKey::StorageBalances => "sb".to_string(),
+            StorageKey::AppchainStateHistories => "ash".to_string(),
+            StorageKey::AppchainStateHistoryOf(appchain_id) => format!("{}ash", appchain_id),
+            StorageKey::AppchainAuditAttestations => "aaa".to_string(),
+            StorageKey::ArchivedAppchains => "arc".to_string(),
+            StorageKey::AnchorTemplates => "ats".to_string(),
+            StorageKey::AnchorTemplateWasm(template_key) => format!("{}atw", template_key),
+            StorageKey::AnchorFunctionCalls => "afc".to_string(),
+            StorageKey::AllowedAnchorMethods => "aam".to_string(),
+            StorageKey::AppchainLiveStatuses => "als".to_string(),
+            StorageKey::ClaimableRegisterDeposits => "crd".to_string(),
+            StorageKey::ClaimableVoteDeposits => "cvd".to_string(),
         }
     }
     pub fn into_bytes(&self) -> Vec<u8> {
@@ -0,0 +1,207 @@
+use crate::{appchain_basedata::AppchainBasedata, *};
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::{assert_one_yocto, StorageUsage};
+
+/// The storage balance of an account in appchain registry
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AccountStorageBalance {
+    /// The total amount of NEAR deposited for storage.
+    pub total: Balance,
+    /// The bytes of storage used by the appchains owned by the account.
+    pub used_bytes: StorageUsage,
+}
+
+impl AccountStorageBalance {
+    /// Get the amount of NEAR which is not locked for storage.
+    pub fn available(&self) -> Balance {
+        self.total.saturating_sub(
+            AppchainRegistry::storage_balance_min_bound()
+                + Balance::from(self.used_bytes) * env::storage_byte_cost(),
+        )
+    }
+    /// Convert to the storage balance of NEP-145.
+    pub fn to_storage_balance(&self) -> StorageBalance {
+        StorageBalance {
+            total: self.total.into(),
+            available: self.available().into(),
+        }
+    }
+}
+
+#[near_bindgen]
+impl StorageManagement for AppchainRegistry {
+    //
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let min_bound = Self::storage_balance_min_bound();
+        let storage_balance = match self.storage_balances.get(&account_id) {
+            Some(mut storage_balance) => {
+                if registration_only.unwrap_or(false) {
+                    log!("The account is already registered, refunding the deposit.");
+                    if amount > 0 {
+                        Promise::new(env::predecessor_account_id()).transfer(amount);
+                    }
+                } else {
+                    storage_balance.total += amount;
+                }
+                storage_balance
+            }
+            None => {
+                assert!(
+                    amount >= min_bound,
+                    "The attached deposit is less than the minimum storage balance."
+                );
+                let mut total = amount;
+                if registration_only.unwrap_or(false) {
+                    if amount > min_bound {
+                        Promise::new(env::predecessor_account_id()).transfer(amount - min_bound);
+                    }
+                    total = min_bound;
+                }
+                AccountStorageBalance {
+                    total,
+                    used_bytes: 0,
+                }
+            }
+        };
+        self.storage_balances.insert(&account_id, &storage_balance);
+        storage_balance.to_storage_balance()
+    }
+    //
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut storage_balance = self
+            .storage_balances
+            .get(&account_id)
+            .expect("The account is not registered.");
+        let available = storage_balance.available();
+        let amount = amount.map_or(available, |amount| amount.0);
+        assert!(
+            amount <= available,
+            "The amount is greater than the available storage balance."
+        );
+        if amount > 0 {
+            storage_balance.total -= amount;
+            self.storage_balances.insert(&account_id, &storage_balance);
+            Promise::new(account_id).transfer(amount);
+        }
+        storage_balance.to_storage_balance()
+    }
+    //
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        assert!(
+            !force.unwrap_or(false),
+            "Force unregistration is not supported."
+        );
+        let account_id = env::predecessor_account_id();
+        match self.storage_balances.get(&account_id) {
+            Some(storage_balance) => {
+                assert!(
+                    storage_balance.used_bytes == 0,
+                    "Can not unregister an account which still owns appchains."
+                );
+                self.storage_balances.remove(&account_id);
+                Promise::new(account_id).transfer(storage_balance.total);
+                true
+            }
+            None => {
+                log!("The account is not registered.");
+                false
+            }
+        }
+    }
+    //
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: Self::storage_balance_min_bound().into(),
+            max: None,
+        }
+    }
+    //
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.storage_balances
+            .get(&account_id)
+            .map(|storage_balance| storage_balance.to_storage_balance())
+    }
+}
+
+impl AppchainRegistry {
+    /// The minimum storage balance, which covers the storage of the balance record itself.
+    pub fn storage_balance_min_bound() -> Balance {
+        Balance::from(STORAGE_BALANCE_RECORD_BYTES) * env::storage_byte_cost()
+    }
+    /// Charge the given bytes of storage to the storage balance of an account.
+    pub fn internal_charge_storage_usage(
+        &mut self,
+        account_id: &AccountId,
+        bytes: StorageUsage,
+    ) -> Result<(), String> {
+        if bytes == 0 {
+            return Ok(());
+        }
+        let mut storage_balance = match self.storage_balances.get(account_id) {
+            Some(storage_balance) => storage_balance,
+            None => return Err(format!("Account '{}' has no storage balance.", account_id)),
+        };
+        let cost = Balance::from(bytes) * env::storage_byte_cost();
+        if storage_balance.available() < cost {
+            return Err(format!(
+                "Insufficient storage balance of account '{}'. Required: '{}', available: '{}'.",
+                account_id,
+                cost,
+                storage_balance.available()
+            ));
+        }
+        storage_balance.used_bytes += bytes;
+        self.storage_balances.insert(account_id, &storage_balance);
+        Ok(())
+    }
+    /// Release the given bytes of storage from the storage balance of an account.
+    pub fn internal_release_storage_usage(&mut self, account_id: &AccountId, bytes: StorageUsage) {
+        if let Some(mut storage_balance) = self.storage_balances.get(account_id) {
+            storage_balance.used_bytes = storage_balance.used_bytes.saturating_sub(bytes);
+            self.storage_balances.insert(account_id, &storage_balance);
+        }
+    }
+    /// Save the appchain basedata and charge (or release) the change of storage usage
+    /// since `storage_usage_before` to the appchain owner.
+    ///
+    /// If the storage balance of the owner is NOT enough, the changes are NOT reverted,
+    /// the caller should revert them (or panic).
+    pub fn internal_save_appchain_with_storage_charged(
+        &mut self,
+        appchain_basedata: &mut AppchainBasedata,
+        storage_usage_before: StorageUsage,
+    ) -> Result<(), String> {
+        self.appchain_basedatas
+            .insert(appchain_basedata.id(), appchain_basedata);
+        let storage_usage_after = env::storage_usage();
+        if storage_usage_after > storage_usage_before {
+            let bytes = storage_usage_after - storage_usage_before;
+            self.internal_charge_storage_usage(&appchain_basedata.owner(), bytes)?;
+            appchain_basedata.storage_usage += bytes;
+        } else {
+            let bytes = core::cmp::min(
+                storage_usage_before - storage_usage_after,
+                appchain_basedata.storage_usage,
+            );
+            self.internal_release_storage_usage(&appchain_basedata.owner(), bytes);
+            appchain_basedata.storage_usage -= bytes;
+        }
+        self.appchain_basedatas
+            .insert(appchain_basedata.id(), appchain_basedata);
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{types::SubstrateTemplateType, *};
+use crate::{
+    types::{RegisterDepositAsset, SubstrateTemplateType},
+    *,
+};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap};
 use near_sdk::{env, near_bindgen, AccountId, Balance, Duration, PublicKey, Timestamp};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -22,6 +25,37 @@ pub struct OldAppchainMetadata {
     pub custom_metadata: HashMap<String, String>,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OldRegistrySettings {
+    pub minimum_register_deposit: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldRegistryRoles {
+    pub appchain_lifecycle_manager: AccountId,
+    pub registry_settings_manager: AccountId,
+    pub octopus_council: Option<AccountId>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldAppchainBasedata {
+    pub appchain_id: AppchainId,
+    pub evm_chain_id: Option<U64>,
+    pub appchain_metadata: LazyOption<AppchainMetadata>,
+    pub appchain_anchor: Option<AccountId>,
+    pub appchain_owner: AccountId,
+    pub register_deposit: Balance,
+    pub appchain_state: AppchainState,
+    pub upvote_deposit: Balance,
+    pub downvote_deposit: Balance,
+    pub registered_time: Timestamp,
+    pub go_live_time: Timestamp,
+    pub validator_count: u32,
+    pub total_stake: Balance,
+    pub dao_proposal_url: Option<String>,
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct OldAppchainRegistry {
@@ -36,11 +70,11 @@ pub struct OldAppchainRegistry {
     /// The account of OCT token contract
     oct_token: AccountId,
     /// The settings of appchain registry
-    registry_settings: LazyOption<RegistrySettings>,
+    registry_settings: LazyOption<OldRegistrySettings>,
     /// The set of all appchain ids
     appchain_ids: UnorderedSet<AppchainId>,
     /// The map from appchain id to their basedata
-    appchain_basedatas: LookupMap<AppchainId, AppchainBasedata>,
+    appchain_basedatas: LookupMap<AppchainId, OldAppchainBasedata>,
     /// The map from pair (appchain id, account id) to their upvote deposit
     upvote_deposits: LookupMap<(AppchainId, AccountId), Balance>,
     /// The map from pair (appchain id, account id) to their downvote deposit
@@ -48,7 +82,7 @@ pub struct OldAppchainRegistry {
     /// The total stake of OCT token in all appchains
     total_stake: Balance,
     /// The roles of appchain registry
-    registry_roles: LazyOption<RegistryRoles>,
+    registry_roles: LazyOption<OldRegistryRoles>,
     /// Whether the asset transfer is paused
     asset_transfer_is_paused: bool,
 }
@@ -63,24 +97,79 @@ impl AppchainRegistry {
         assert_self();
         //
         // Create the new contract using the data from the old contract.
-        let new_appchain_registry = AppchainRegistry {
+        let mut new_appchain_registry = AppchainRegistry {
             owner: old_contract.owner,
             owner_pk: old_contract.owner_pk,
             contract_code_staging_timestamp: old_contract.contract_code_staging_timestamp,
             contract_code_staging_duration: old_contract.contract_code_staging_duration,
             oct_token: old_contract.oct_token,
-            registry_settings: old_contract.registry_settings,
+            registry_settings: LazyOption::new(
+                StorageKey::RegistrySettings.into_bytes(),
+                Some(&RegistrySettings::from(
+                    old_contract.registry_settings.get().unwrap(),
+                )),
+            ),
             appchain_ids: old_contract.appchain_ids,
-            appchain_basedatas: old_contract.appchain_basedatas,
+            appchain_basedatas: LookupMap::new(StorageKey::AppchainBasedatas.into_bytes()),
             upvote_deposits: old_contract.upvote_deposits,
             downvote_deposits: old_contract.downvote_deposits,
+            appchain_voters: LookupMap::new(StorageKey::AppchainVoters.into_bytes()),
+            voted_appchains: LookupMap::new(StorageKey::VotedAppchains.into_bytes()),
             total_stake: old_contract.total_stake,
-            registry_roles: old_contract.registry_roles,
+            registry_roles: LazyOption::new(
+                StorageKey::RegistryRoles.into_bytes(),
+                Some(&RegistryRoles::from(
+                    old_contract.registry_roles.get().unwrap(),
+                )),
+            ),
             asset_transfer_is_paused: old_contract.asset_transfer_is_paused,
+            voting_score_counting_status: VotingScoreCountingStatus::default(),
+            registry_treasury: RegistryTreasury::default(),
+            storage_balances: LookupMap::new(StorageKey::StorageBalances.into_bytes()),
+            appchain_state_histories: LookupMap::new(
+                StorageKey::AppchainStateHistories.into_bytes(),
+            ),
+            appchain_audit_attestations: LookupMap::new(
+                StorageKey::AppchainAuditAttestations.into_bytes(),
+            ),
+            archived_appchains: UnorderedMap::new(StorageKey::ArchivedAppchains.into_bytes()),
+            anchor_templates: UnorderedMap::new(StorageKey::AnchorTemplates.into_bytes()),
+            anchor_function_calls: LookupMap::new(StorageKey::AnchorFunctionCalls.into_bytes()),
+            anchor_function_call_id_range: (0, 0),
+            allowed_anchor_methods: UnorderedMap::new(
+                StorageKey::AllowedAnchorMethods.into_bytes(),
+            ),
+            appchain_live_statuses: LookupMap::new(StorageKey::AppchainLiveStatuses.into_bytes()),
+            claimable_register_deposits: LookupMap::new(
+                StorageKey::ClaimableRegisterDeposits.into_bytes(),
+            ),
+            claimable_vote_deposits: LookupMap::new(StorageKey::ClaimableVoteDeposits.into_bytes()),
         };
         //
         let appchain_ids = new_appchain_registry.appchain_ids.to_vec();
         for appchain_id in appchain_ids {
+            if let Some(old_basedata) = old_contract.appchain_basedatas.get(&appchain_id) {
+                let appchain_basedata =
+                    migrate_appchain_basedata(old_basedata, &new_appchain_registry.oct_token);
+                let ledger = new_appchain_registry
+                    .registry_treasury
+                    .ledger_of_mut(&appchain_basedata.register_deposit_asset());
+                match appchain_basedata.refundable_register_deposit {
+                    Some(_) => {
+                        ledger.withdrawable_fees = (ledger.withdrawable_fees.0
+                            + appchain_basedata.register_deposit())
+                        .into()
+                    }
+                    None => {
+                        ledger.pending_register_deposits = (ledger.pending_register_deposits.0
+                            + appchain_basedata.register_deposit())
+                        .into()
+                    }
+                }
+                new_appchain_registry
+                    .appchain_basedatas
+                    .insert(&appchain_id, &appchain_basedata);
+            }
             let storage_key = StorageKey::AppchainMetadata(appchain_id.clone()).into_bytes();
             if let Some(bytes) = env::storage_read(storage_key.as_slice()) {
                 let old_meta = OldAppchainMetadata::try_from_slice(bytes.as_slice()).unwrap();
@@ -124,3 +213,74 @@ impl From<OldAppchainMetadata> for AppchainMetadata {
         }
     }
 }
+
+impl From<OldRegistrySettings> for RegistrySettings {
+    fn from(value: OldRegistrySettings) -> Self {
+        Self {
+            minimum_register_deposit: value.minimum_register_deposit,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<OldRegistryRoles> for RegistryRoles {
+    fn from(value: OldRegistryRoles) -> Self {
+        Self {
+            appchain_lifecycle_manager: value.appchain_lifecycle_manager,
+            registry_settings_manager: value.registry_settings_manager,
+            octopus_council: value.octopus_council,
+            auditors: Vec::new(),
+        }
+    }
+}
+
+/// Convert the old appchain basedata, whose register deposit was always paid in OCT token.
+fn migrate_appchain_basedata(
+    value: OldAppchainBasedata,
+    oct_token: &AccountId,
+) -> AppchainBasedata {
+    // The register deposits of appchains which are already closed or live
+    // are treated as collected fees.
+    let refundable_register_deposit = match value.appchain_state {
+        AppchainState::Active | AppchainState::Closing | AppchainState::Closed => Some(0),
+        _ => None,
+    };
+    // The appchains which are already in voting get a full default voting period.
+    let voting_deadline = match value.appchain_state {
+        AppchainState::Voting => {
+            Some(env::block_timestamp() + DEFAULT_VOTING_DURATION * NANO_SECONDS_MULTIPLE)
+        }
+        _ => None,
+    };
+    AppchainBasedata {
+        appchain_id: value.appchain_id,
+        evm_chain_id: value.evm_chain_id,
+        appchain_metadata: value.appchain_metadata,
+        appchain_anchor: value.appchain_anchor,
+        appchain_owner: value.appchain_owner,
+        register_deposit: value.register_deposit,
+        register_deposit_asset: RegisterDepositAsset::FungibleToken(oct_token.clone()),
+        appchain_state: value.appchain_state,
+        upvote_deposit: value.upvote_deposit,
+        downvote_deposit: value.downvote_deposit,
+        registered_time: value.registered_time,
+        go_live_time: value.go_live_time,
+        validator_count: value.validator_count,
+        total_stake: value.total_stake,
+        dao_proposal_url: value.dao_proposal_url,
+        voting_deadline,
+        register_deposit_refund_case: None,
+        refundable_register_deposit,
+        refunded_register_deposit: None,
+        storage_usage: 0,
+        audit_report: None,
+        rejection: None,
+        previous_registrations: Vec::new(),
+        anchor_deployment_status: None,
+        anchor_template_version: None,
+        anchor_upgrade: None,
+        anchor_teardown_status: None,
+        last_sync_time: None,
+        pending_vote_deposit_refunds: 0,
+    }
+}
@@ -1,6 +1,6 @@
 use crate::*;
 use core::fmt::Display;
-use near_sdk::json_types::{I128, U64};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, I128, U64};
 
 pub type AppchainId = String;
 
@@ -9,6 +9,312 @@ pub type AppchainId = String;
 pub struct RegistrySettings {
     /// The minimum deposit amount for registering an appchain.
     pub minimum_register_deposit: U128,
+    /// The shortest interval between two rounds of counting voting score.
+    pub voting_score_counting_interval_in_seconds: U64,
+    /// The percent of voting score to reduce before counting it in a new round.
+    pub voting_score_decay_percent: u16,
+    /// The refund policy of register deposit.
+    pub register_deposit_refund_policy: RegisterDepositRefundPolicy,
+    /// The accepted assets (other than OCT token) for register deposit,
+    /// with their minimum deposit amount.
+    pub other_register_deposit_assets: Vec<RegisterDepositAssetSetting>,
+    /// The duration of the voting period of an appchain.
+    pub voting_duration_in_seconds: U64,
+    /// The state that an appchain goes to when its voting period expires.
+    pub voting_expiry_policy: VotingExpiryPolicy,
+    /// The number of approvals of auditors needed for an appchain to pass auditing.
+    /// If it is 0, appchains are audited by appchain lifecycle manager.
+    pub audit_threshold: u16,
+    /// The longest time that an active appchain can go without a sync from its anchor
+    /// before it is considered stale. If it is 0, the staleness detection is disabled.
+    pub anchor_sync_stale_threshold_in_seconds: U64,
+}
+
+impl RegistrySettings {
+    /// Get the minimum register deposit of the given asset.
+    /// Return `None` if the asset is not accepted.
+    pub fn minimum_register_deposit_of(
+        &self,
+        asset: &RegisterDepositAsset,
+        oct_token: &AccountId,
+    ) -> Option<Balance> {
+        match asset {
+            RegisterDepositAsset::FungibleToken(contract_id) if contract_id.eq(oct_token) => {
+                Some(self.minimum_register_deposit.0)
+            }
+            _ => self
+                .other_register_deposit_assets
+                .iter()
+                .find(|setting| setting.asset.eq(asset))
+                .map(|setting| setting.minimum_register_deposit.0),
+        }
+    }
+}
+
+/// The asset which can be used to pay the register deposit
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RegisterDepositAsset {
+    /// Native NEAR token.
+    Near,
+    /// A NEP-141 fungible token, specified by its contract account.
+    FungibleToken(AccountId),
+}
+
+/// The setting of an accepted register deposit asset
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RegisterDepositAssetSetting {
+    pub asset: RegisterDepositAsset,
+    pub minimum_register_deposit: U128,
+}
+
+/// The percents of register deposit to refund in different cases
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RegisterDepositRefundPolicy {
+    /// The appchain is withdrawn by its owner in 'registered' state.
+    pub withdrawn_while_registered_percent: u16,
+    /// The appchain is withdrawn by its owner in 'audited' state.
+    pub withdrawn_after_audit_percent: u16,
+    /// The appchain is rejected by appchain lifecycle manager.
+    pub rejected_percent: u16,
+    /// The appchain went live (became 'active').
+    pub went_live_percent: u16,
+}
+
+/// The policy for the appchain whose voting period expires
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum VotingExpiryPolicy {
+    /// The appchain is closed, as it is rejected.
+    Close,
+    /// The appchain goes back to 'audited', and can be voted again.
+    BackToAudited,
+}
+
+/// The case in which the register deposit of an appchain can be refunded
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RegisterDepositRefundCase {
+    WithdrawnWhileRegistered,
+    WithdrawnAfterAudit,
+    Rejected,
+    WentLive,
+}
+
+/// A version of the anchor contract code for a certain appchain type
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorTemplate {
+    pub appchain_type: AppchainType,
+    /// The semantic version of the template, in format 'major.minor.patch'.
+    pub version: String,
+    /// The sha256 hash of the wasm code.
+    pub code_hash: Base58CryptoHash,
+    /// A deprecated template will NOT be deployed to any appchain.
+    pub deprecated: bool,
+    pub stored_time: U64,
+}
+
+impl AnchorTemplate {
+    /// Parse a semantic version in format 'major.minor.patch'.
+    pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+        let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) => {
+                Some((major, minor, patch))
+            }
+            _ => None,
+        }
+    }
+    /// The key of the template in storage.
+    pub fn key_of(appchain_type: &AppchainType, version: &String) -> String {
+        format!("{}@{}", appchain_type, version)
+    }
+}
+
+/// The status of the upgrade of the anchor contract of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorUpgradeStatus {
+    Upgrading,
+    Upgraded,
+    Failed,
+}
+
+/// The upgrade of the anchor contract of an appchain to a certain template version
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorUpgrade {
+    pub from_version: Option<String>,
+    pub to_version: String,
+    pub status: AnchorUpgradeStatus,
+    pub start_time: U64,
+}
+
+/// The status of an appchain reported by its anchor, in version 1
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorSyncPayloadV1 {
+    pub appchain_state: AppchainState,
+    pub validator_count: u32,
+    pub total_stake: U128,
+    pub era_number: U64,
+    pub last_era_reward: U128,
+    pub total_bridged_token_supply: U128,
+    pub anchor_version: String,
+    pub appchain_block_height: U64,
+}
+
+/// The versioned payload for appchain anchor to sync status to registry
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum VersionedAnchorSyncPayload {
+    V1(AnchorSyncPayloadV1),
+}
+
+/// The live status of an appchain, which is reported by its anchor
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainLiveStatus {
+    pub era_number: U64,
+    pub last_era_reward: U128,
+    pub total_bridged_token_supply: U128,
+    pub anchor_version: String,
+    pub appchain_block_height: U64,
+    pub updated_at: U64,
+}
+
+/// A method of appchain anchor which can be called by octopus council through registry
+///
+/// This struct should NOT be used in storage on chain
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowedAnchorMethod {
+    pub method_name: String,
+    /// The appchain types whose anchors the method can be called on.
+    /// `None` means all appchain types.
+    pub appchain_types: Option<Vec<AppchainType>>,
+}
+
+/// A function call to the anchor of an appchain
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorFunctionCall {
+    pub appchain_id: AppchainId,
+    pub function_name: String,
+    /// The arguments (in json) of the function.
+    pub args: String,
+    /// The deposit to attach to the function call.
+    pub deposit: U128,
+    /// The gas for the function call, the default value will be used if not specified.
+    pub gas: Option<U64>,
+}
+
+/// The status of a function call to the anchor of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorFunctionCallStatus {
+    Pending,
+    Succeeded,
+    /// The attached deposit is refunded to the caller.
+    Failed,
+}
+
+/// The record of a function call to the anchor of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorFunctionCallRecord {
+    pub call_id: U64,
+    pub appchain_id: AppchainId,
+    pub function_name: String,
+    pub caller: AccountId,
+    pub deposit: U128,
+    pub status: AnchorFunctionCallStatus,
+    /// The bytes returned by the function, only the first 512 bytes are kept.
+    pub return_value: Option<Base64VecU8>,
+    pub called_at: U64,
+}
+
+/// The status of the teardown of the anchor account of an appchain which is going to be removed
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorTeardownStatus {
+    TearingDown,
+    /// The appchain is kept in registry, the removal can be retried.
+    Failed,
+    /// The anchor account is deleted, but the appchain is NOT removed
+    /// (as it is no longer removable), the removal can be retried.
+    TornDown,
+}
+
+/// The status of the deployment of anchor contract code at booting of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorDeploymentStatus {
+    /// The staged anchor code is being deployed and initialized.
+    Deploying,
+    Deployed,
+    /// The deployment or initialization failed, the anchor needs to be deployed manually.
+    Failed,
+}
+
+/// The report of the auditing of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditReport {
+    pub report_url: String,
+    /// The hash of the content of the report, for verifying the report off-chain.
+    pub content_hash: String,
+    pub auditor: AccountId,
+    pub notes: String,
+}
+
+/// The attestation of an auditor on a registered appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuditAttestation {
+    pub auditor: AccountId,
+    pub approved: bool,
+    pub audit_report: Option<AuditReport>,
+    pub comment: String,
+    pub submitted_at: U64,
+}
+
+/// The record of a previous registration of an appchain, which is kept
+/// when the closed appchain is reapplied by its owner
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainRegistrationRecord {
+    pub registered_time: U64,
+    pub reapplied_time: U64,
+    pub register_deposit: U128,
+    pub register_deposit_asset: RegisterDepositAsset,
+    pub refunded_register_deposit: Option<U128>,
+    pub dao_proposal_url: Option<String>,
+    pub audit_report: Option<AuditReport>,
+    pub rejection: Option<AppchainRejection>,
+}
+
+/// The reason code of the rejection of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainRejectionCode {
+    IncompleteMetadata,
+    FailedAuditing,
+    FailedVoting,
+    DuplicatedAppchain,
+    Other,
+}
+
+/// The rejection of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainRejection {
+    pub code: AppchainRejectionCode,
+    pub reason: String,
+    pub rejected_by: AccountId,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -20,6 +326,8 @@ pub struct RegistryRoles {
     pub registry_settings_manager: AccountId,
     /// The account of octopus council (DAO contract)
     pub octopus_council: Option<AccountId>,
+    /// The accounts which audit registered appchains.
+    pub auditors: Vec<AccountId>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq)]
@@ -56,6 +364,90 @@ pub struct AppchainMetadata {
     pub custom_metadata: HashMap<String, String>,
 }
 
+impl AppchainMetadata {
+    /// Check the necessary fields of the metadata provided by the registrant.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.website_url.trim().is_empty() {
+            return Err("Missing necessary field 'website_url'.".to_string());
+        }
+        if self.github_address.trim().is_empty() {
+            return Err("Missing necessary field 'github_address'.".to_string());
+        }
+        if self.contact_email.trim().is_empty() {
+            return Err("Missing necessary field 'contact_email'.".to_string());
+        }
+        let fungible_token_metadata = &self.fungible_token_metadata;
+        if !fungible_token_metadata.spec.eq(FT_METADATA_SPEC)
+            || fungible_token_metadata.reference.is_some()
+                != fungible_token_metadata.reference_hash.is_some()
+            || fungible_token_metadata
+                .reference_hash
+                .as_ref()
+                .is_some_and(|hash| hash.0.len() != 32)
+        {
+            return Err("Invalid fungible token metadata.".to_string());
+        }
+        if fungible_token_metadata.name.trim().is_empty() {
+            return Err("Missing necessary field 'fungible token name'.".to_string());
+        }
+        if fungible_token_metadata.symbol.trim().is_empty() {
+            return Err("Missing necessary field 'fungible token symbol'.".to_string());
+        }
+        if self.initial_supply_of_wrapped_appchain_token.0 < self.premined_wrapped_appchain_token.0
+        {
+            return Err("The initial supply of wrapped appchain token should not be less than the premined amount.".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// The args provided by the registrant for registering (or reapplying) an appchain
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainRegistrationArgs {
+    pub appchain_id: AppchainId,
+    pub description: String,
+    pub appchain_type: AppchainType,
+    pub evm_chain_id: Option<U64>,
+    pub website_url: String,
+    pub github_address: String,
+    pub contact_email: String,
+    pub premined_wrapped_appchain_token_beneficiary: AccountId,
+    pub premined_wrapped_appchain_token: U128,
+    pub initial_supply_of_wrapped_appchain_token: U128,
+    pub ido_amount_of_wrapped_appchain_token: U128,
+    pub initial_era_reward: U128,
+    pub fungible_token_metadata: FungibleTokenMetadata,
+    pub custom_metadata: HashMap<String, String>,
+}
+
+impl AppchainRegistrationArgs {
+    /// Split the args into the appchain id, the evm chain id and the appchain metadata.
+    ///
+    /// The urls which are set by appchain lifecycle manager are left empty.
+    pub fn into_parts(self) -> (AppchainId, Option<U64>, AppchainMetadata) {
+        let appchain_metadata = AppchainMetadata {
+            description: self.description,
+            appchain_type: self.appchain_type,
+            website_url: self.website_url,
+            function_spec_url: String::new(),
+            github_address: self.github_address,
+            github_release: String::new(),
+            contact_email: self.contact_email,
+            premined_wrapped_appchain_token_beneficiary: Some(
+                self.premined_wrapped_appchain_token_beneficiary,
+            ),
+            premined_wrapped_appchain_token: self.premined_wrapped_appchain_token,
+            initial_supply_of_wrapped_appchain_token: self.initial_supply_of_wrapped_appchain_token,
+            ido_amount_of_wrapped_appchain_token: self.ido_amount_of_wrapped_appchain_token,
+            initial_era_reward: self.initial_era_reward,
+            fungible_token_metadata: self.fungible_token_metadata,
+            custom_metadata: self.custom_metadata,
+        };
+        (self.appchain_id, self.evm_chain_id, appchain_metadata)
+    }
+}
+
 /// The state of an appchain
 #[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
@@ -69,6 +461,46 @@ pub enum AppchainState {
     Closed,
 }
 
+/// The role of the actor who changes the state of an appchain
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainStateActor {
+    RegistryOwner,
+    AppchainLifecycleManager,
+    OctopusCouncil,
+    AppchainOwner,
+    AppchainAnchor,
+    AuditorQuorum,
+    /// Any account, after the voting period of the appchain expires.
+    VotingExpiry,
+}
+
+/// An allowed transition of appchain state
+///
+/// This struct should NOT be used in storage on chain
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainStateTransition {
+    pub from: AppchainState,
+    pub to: AppchainState,
+    pub actor: AppchainStateActor,
+}
+
+/// A history entry of the state changes of an appchain
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainStateChange {
+    pub from: AppchainState,
+    pub to: AppchainState,
+    pub timestamp: U64,
+    pub block_height: U64,
+    /// The account which performed the change.
+    pub actor: AccountId,
+    pub reason: Option<String>,
+    /// Whether the change bypassed the transition table.
+    pub forced: bool,
+}
+
 /// Appchain status
 ///
 /// This struct should NOT be used in storage on chain
@@ -81,6 +513,7 @@ pub struct AppchainStatus {
     pub appchain_anchor: Option<AccountId>,
     pub appchain_owner: AccountId,
     pub register_deposit: U128,
+    pub register_deposit_asset: RegisterDepositAsset,
     pub appchain_state: AppchainState,
     pub upvote_deposit: U128,
     pub downvote_deposit: U128,
@@ -90,6 +523,148 @@ pub struct AppchainStatus {
     pub validator_count: u32,
     pub total_stake: U128,
     pub dao_proposal_url: Option<String>,
+    pub voting_deadline: Option<U64>,
+    pub register_deposit_refund_case: Option<RegisterDepositRefundCase>,
+    pub refundable_register_deposit: Option<U128>,
+    pub refunded_register_deposit: Option<U128>,
+    pub audit_report: Option<AuditReport>,
+    pub rejection: Option<AppchainRejection>,
+    pub previous_registrations: Vec<AppchainRegistrationRecord>,
+    pub anchor_deployment_status: Option<AnchorDeploymentStatus>,
+    pub anchor_template_version: Option<String>,
+    pub anchor_upgrade: Option<AnchorUpgrade>,
+    pub anchor_teardown_status: Option<AnchorTeardownStatus>,
+    pub last_sync_time: Option<U64>,
+    pub health: Option<AppchainHealth>,
+}
+
+/// The health of an active appchain, based on the syncs from its anchor
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainHealth {
+    /// The anchor synced the appchain within the stale threshold.
+    Healthy,
+    /// The anchor has NOT synced the appchain for longer than the stale threshold.
+    Stale,
+}
+
+/// The summary of an appchain which is removed from registry
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ArchivedAppchain {
+    pub appchain_id: AppchainId,
+    /// The index of this removal among the removals of the appchain id, starting from 0.
+    pub removal_index: u32,
+    pub appchain_owner: AccountId,
+    pub appchain_type: AppchainType,
+    pub appchain_anchor: Option<AccountId>,
+    pub final_state: AppchainState,
+    pub registered_time: U64,
+    pub go_live_time: U64,
+    /// The time of the last state change of the appchain.
+    pub closed_time: U64,
+    pub removed_time: U64,
+}
+
+/// The ledgers of register deposits collected by appchain registry
+///
+/// The vote deposits of voters are NOT included.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RegistryTreasury {
+    /// The account to receive the swept fees.
+    pub treasury_account: Option<AccountId>,
+    /// The ledgers of all assets which have been used to pay register deposit.
+    pub ledgers: Vec<TreasuryLedger>,
+}
+
+impl RegistryTreasury {
+    /// Get the mutable ledger of the given asset, create it if not exists.
+    pub fn ledger_of_mut(&mut self, asset: &RegisterDepositAsset) -> &mut TreasuryLedger {
+        match self
+            .ledgers
+            .iter()
+            .position(|ledger| ledger.asset.eq(asset))
+        {
+            Some(index) => &mut self.ledgers[index],
+            None => {
+                self.ledgers.push(TreasuryLedger::new(asset.clone()));
+                self.ledgers.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+/// The ledger of register deposits paid in a certain asset
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryLedger {
+    /// The asset of this ledger.
+    pub asset: RegisterDepositAsset,
+    /// The register deposits of appchains whose outcome is not decided yet.
+    pub pending_register_deposits: U128,
+    /// The register deposits reserved for refunding to appchain owners.
+    pub reserved_register_deposit_refunds: U128,
+    /// The collected fees which can be swept to the treasury account.
+    pub withdrawable_fees: U128,
+    /// The total amount of fees swept to the treasury account.
+    pub swept_fees: U128,
+}
+
+impl TreasuryLedger {
+    /// Create an empty ledger of the given asset
+    pub fn new(asset: RegisterDepositAsset) -> Self {
+        Self {
+            asset,
+            pending_register_deposits: U128::from(0),
+            reserved_register_deposit_refunds: U128::from(0),
+            withdrawable_fees: U128::from(0),
+            swept_fees: U128::from(0),
+        }
+    }
+}
+
+/// The vote of a voter for an appchain
+///
+/// This struct should NOT be used in storage on chain
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainVoter {
+    pub voter_id: AccountId,
+    pub upvote_deposit: U128,
+    pub downvote_deposit: U128,
+}
+
+/// The vote of an account for an appchain
+///
+/// This struct should NOT be used in storage on chain
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountVote {
+    pub appchain_id: AppchainId,
+    pub upvote_deposit: U128,
+    pub downvote_deposit: U128,
+}
+
+/// The status of counting voting score of appchains
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VotingScoreCountingStatus {
+    /// The start time of the latest counting round.
+    pub round_start_time: U64,
+    /// The time that the latest counting round is finished.
+    pub last_round_end_time: U64,
+    /// The index (in set of appchain ids) of the next appchain to count.
+    /// It is `None` if there is no counting round in progress.
+    pub next_appchain_index: Option<U64>,
+}
+
+/// The result of an operation which may be processed in multiple transactions
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MultiTxsOperationProcessingResult {
+    NeedMoreGas,
+    Ok,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -120,6 +695,71 @@ impl AppchainState {
             AppchainState::Closed => false,
         }
     }
+    /// Get the table of all allowed transitions of appchain state
+    pub fn transition_table() -> Vec<AppchainStateTransition> {
+        use AppchainState::*;
+        use AppchainStateActor::*;
+        [
+            (Registered, Audited, AppchainLifecycleManager),
+            (Registered, Audited, AuditorQuorum),
+            (Audited, Voting, AppchainLifecycleManager),
+            (Registered, Closed, AppchainLifecycleManager),
+            (Audited, Closed, AppchainLifecycleManager),
+            (Voting, Closed, AppchainLifecycleManager),
+            (Voting, Closed, VotingExpiry),
+            (Voting, Audited, VotingExpiry),
+            (Registered, Closed, AuditorQuorum),
+            (Registered, Closed, AppchainOwner),
+            (Audited, Closed, AppchainOwner),
+            (Closed, Registered, AppchainOwner),
+            (Voting, Booting, OctopusCouncil),
+            (Audited, Booting, RegistryOwner),
+            (Voting, Booting, RegistryOwner),
+            (Booting, Active, AppchainAnchor),
+            (Active, Closing, AppchainAnchor),
+            (Closing, Closed, AppchainAnchor),
+        ]
+        .into_iter()
+        .map(|(from, to, actor)| AppchainStateTransition { from, to, actor })
+        .collect()
+    }
+    /// Get the allowed transitions from this state
+    pub fn allowed_transitions(&self) -> Vec<AppchainStateTransition> {
+        Self::transition_table()
+            .into_iter()
+            .filter(|transition| transition.from.eq(self))
+            .collect()
+    }
+    /// Get whether the state can be changed to the given state by the given actor
+    pub fn can_transit_to(&self, to: &AppchainState, actor: &AppchainStateActor) -> bool {
+        self.allowed_transitions()
+            .iter()
+            .any(|transition| transition.to.eq(to) && transition.actor.eq(actor))
+    }
+}
+
+impl RegisterDepositRefundPolicy {
+    /// Get the refund percent of the given case
+    pub fn percent_of(&self, case: &RegisterDepositRefundCase) -> u16 {
+        match case {
+            RegisterDepositRefundCase::WithdrawnWhileRegistered => {
+                self.withdrawn_while_registered_percent
+            }
+            RegisterDepositRefundCase::WithdrawnAfterAudit => self.withdrawn_after_audit_percent,
+            RegisterDepositRefundCase::Rejected => self.rejected_percent,
+            RegisterDepositRefundCase::WentLive => self.went_live_percent,
+        }
+    }
+}
+
+impl Default for VotingScoreCountingStatus {
+    fn default() -> Self {
+        Self {
+            round_start_time: U64::from(0),
+            last_round_end_time: U64::from(0),
+            next_appchain_index: None,
+        }
+    }
 }
 
 impl Display for AppchainState {
@@ -135,3 +775,17 @@ impl Display for AppchainState {
         }
     }
 }
+
+impl Display for AppchainType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AppchainType::Cosmos => write!(f, "cosmos"),
+            AppchainType::Substrate(SubstrateTemplateType::Barnacle) => {
+                write!(f, "substrate-barnacle")
+            }
+            AppchainType::Substrate(SubstrateTemplateType::BarnacleEvm) => {
+                write!(f, "substrate-barnacle-evm")
+            }
+        }
+    }
+}
@@ -31,6 +31,34 @@ pub extern "C" fn store_wasm_of_self() {
     env::value_return(&blob_hash_str);
 }
 
+/// Stores attached data into blob store as the code of appchain anchor, and returns hash of it.
+/// The staged code should be registered as an anchor template by `register_anchor_template`.
+#[no_mangle]
+pub extern "C" fn store_wasm_of_appchain_anchor() {
+    env::setup_panic_hook();
+    let contract: AppchainRegistry = env::state_read().expect("ERR_CONTRACT_IS_NOT_INITIALIZED");
+    contract.assert_owner();
+    let input = env::input().expect("ERR_NO_INPUT");
+    let sha256_hash = env::sha256(&input);
+
+    let blob_len = input.len();
+    let storage_cost = ((blob_len + 32) as u128) * env::storage_byte_cost();
+    assert!(
+        env::attached_deposit() >= storage_cost,
+        "ERR_NOT_ENOUGH_DEPOSIT:{}",
+        storage_cost
+    );
+
+    env::storage_write(&StorageKey::AnchorContractWasm.into_bytes(), &input);
+    let mut blob_hash = [0u8; 32];
+    blob_hash.copy_from_slice(&sha256_hash);
+    let blob_hash_str = serde_json::to_string(&Base58CryptoHash::from(blob_hash))
+        .unwrap()
+        .into_bytes();
+
+    env::value_return(&blob_hash_str);
+}
+
 #[no_mangle]
 pub fn update_self() {
     env::setup_panic_hook();
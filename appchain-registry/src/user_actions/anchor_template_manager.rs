@@ -0,0 +1,194 @@
+use crate::{
+    types::{AnchorTemplate, AnchorUpgradeStatus},
+    *,
+};
+use core::ops::Mul;
+use near_sdk::json_types::Base58CryptoHash;
+
+/// The actions for managing the templates (versions) of anchor contract code
+pub trait AnchorTemplateManager {
+    /// Register the anchor code staged by `store_wasm_of_appchain_anchor`
+    /// as a template of the given appchain type and version.
+    fn register_anchor_template(&mut self, appchain_type: AppchainType, version: String);
+    /// Mark a template as deprecated, it will NOT be deployed to any appchain.
+    fn deprecate_anchor_template(&mut self, appchain_type: AppchainType, version: String);
+    /// Push an anchor template to the anchors of the given appchains.
+    /// If `version` is not specified, the current template of the appchain type is used.
+    ///
+    /// A template can only be pushed after the contract code staging duration
+    /// since it is registered. Can be called by owner or octopus council.
+    ///
+    /// The attached deposit should cover the storage cost of the code in all anchors,
+    /// the surplus is refunded to the caller.
+    fn upgrade_appchain_anchors(&mut self, appchain_ids: Vec<AppchainId>, version: Option<String>);
+}
+
+#[near_bindgen]
+impl AnchorTemplateManager for AppchainRegistry {
+    //
+    fn register_anchor_template(&mut self, appchain_type: AppchainType, version: String) {
+        self.assert_owner();
+        assert!(
+            AnchorTemplate::parse_version(&version).is_some(),
+            "Invalid version '{}', should be in format 'major.minor.patch'.",
+            version
+        );
+        let template_key = AnchorTemplate::key_of(&appchain_type, &version);
+        assert!(
+            self.anchor_templates.get(&template_key).is_none(),
+            "Anchor template '{}' already exists.",
+            template_key
+        );
+        let staged_key = StorageKey::AnchorContractWasm.into_bytes();
+        let code = env::storage_read(&staged_key).expect("Anchor code is not staged yet.");
+        let mut code_hash = [0u8; 32];
+        code_hash.copy_from_slice(&env::sha256(&code));
+        env::storage_write(
+            &StorageKey::AnchorTemplateWasm(template_key.clone()).into_bytes(),
+            &code,
+        );
+        env::storage_remove(&staged_key);
+        self.anchor_templates.insert(
+            &template_key,
+            &AnchorTemplate {
+                appchain_type,
+                version,
+                code_hash: Base58CryptoHash::from(code_hash),
+                deprecated: false,
+                stored_time: env::block_timestamp().into(),
+            },
+        );
+        log!("Anchor template '{}' is registered.", template_key);
+    }
+    //
+    fn deprecate_anchor_template(&mut self, appchain_type: AppchainType, version: String) {
+        self.assert_owner();
+        let template_key = AnchorTemplate::key_of(&appchain_type, &version);
+        let mut template = self
+            .anchor_templates
+            .get(&template_key)
+            .expect("Anchor template not found.");
+        assert!(
+            !template.deprecated,
+            "Anchor template '{}' is already deprecated.",
+            template_key
+        );
+        template.deprecated = true;
+        self.anchor_templates.insert(&template_key, &template);
+        log!("Anchor template '{}' is deprecated.", template_key);
+    }
+    //
+    #[payable]
+    fn upgrade_appchain_anchors(&mut self, appchain_ids: Vec<AppchainId>, version: Option<String>) {
+        self.assert_owner_or_octopus_council();
+        assert!(!appchain_ids.is_empty(), "No appchain to upgrade.");
+        let total_storage_cost: Balance = appchain_ids
+            .into_iter()
+            .map(|appchain_id| self.internal_upgrade_appchain_anchor(appchain_id, &version))
+            .sum();
+        assert!(
+            env::attached_deposit() >= total_storage_cost,
+            "Insufficient deposit for the storage cost of anchor code. The total cost is '{}'.",
+            total_storage_cost
+        );
+        let surplus = env::attached_deposit() - total_storage_cost;
+        if surplus > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(surplus);
+        }
+    }
+}
+
+impl AppchainRegistry {
+    /// Return the storage cost attached to the call of `store_wasm_of_self` of the anchor.
+    fn internal_upgrade_appchain_anchor(
+        &mut self,
+        appchain_id: AppchainId,
+        version: &Option<String>,
+    ) -> Balance {
+        self.assert_appchain_state(
+            &appchain_id,
+            [AppchainState::Booting, AppchainState::Active].to_vec(),
+        );
+        let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        let anchor = appchain_basedata
+            .anchor()
+            .expect("The anchor of the appchain is not set.");
+        assert!(
+            appchain_basedata
+                .anchor_upgrade
+                .as_ref()
+                .map(|upgrade| &upgrade.status)
+                != Some(&AnchorUpgradeStatus::Upgrading),
+            "The anchor of appchain '{}' is being upgraded.",
+            appchain_id
+        );
+        let appchain_type = appchain_basedata.metadata().appchain_type;
+        let template = match version {
+            Some(version) => self
+                .anchor_templates
+                .get(&AnchorTemplate::key_of(&appchain_type, version))
+                .expect("Anchor template not found."),
+            None => self
+                .current_anchor_template_of(&appchain_type)
+                .expect("No available anchor template for the appchain type."),
+        };
+        let template_key = AnchorTemplate::key_of(&template.appchain_type, &template.version);
+        assert!(
+            !template.deprecated,
+            "Anchor template '{}' is deprecated.",
+            template_key
+        );
+        assert!(
+            template.stored_time.0 + self.contract_code_staging_duration <= env::block_timestamp(),
+            "Anchor template '{}' is still in staging period.",
+            template_key
+        );
+        assert!(
+            appchain_basedata.anchor_template_version.as_ref() != Some(&template.version),
+            "The anchor of appchain '{}' is already running version '{}'.",
+            appchain_id,
+            template.version
+        );
+        appchain_basedata.start_anchor_upgrade(template.version.clone());
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        //
+        let anchor_code =
+            env::storage_read(&StorageKey::AnchorTemplateWasm(template_key).into_bytes()).unwrap();
+        let storage_cost = ((anchor_code.len() + 32) as u128) * env::storage_byte_cost();
+        Promise::new(anchor)
+            .function_call(
+                "store_wasm_of_self".to_string(),
+                anchor_code,
+                storage_cost,
+                Gas::ONE_TERA.mul(T_GAS_FOR_STORING_ANCHOR_CODE),
+            )
+            .function_call(
+                "update_self".to_string(),
+                Vec::new(),
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_UPDATING_ANCHOR),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_anchor_upgrade(
+                        appchain_id,
+                        env::predecessor_account_id(),
+                        storage_cost.into(),
+                    ),
+            );
+        storage_cost
+    }
+    /// Get the latest version of the templates which are not deprecated for the given appchain type
+    pub fn current_anchor_template_of(
+        &self,
+        appchain_type: &AppchainType,
+    ) -> Option<AnchorTemplate> {
+        self.anchor_templates
+            .values()
+            .filter(|template| template.appchain_type.eq(appchain_type) && !template.deprecated)
+            .max_by_key(|template| AnchorTemplate::parse_version(&template.version))
+    }
+}
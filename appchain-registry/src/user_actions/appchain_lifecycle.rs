@@ -1,4 +1,13 @@
-use crate::{types::AppchainId, *};
+use crate::{
+    appchain_basedata::AppchainBasedata,
+    types::{
+        AnchorDeploymentStatus, AnchorTeardownStatus, AnchorTemplate, AppchainId,
+        AppchainRejection, AppchainRejectionCode, AppchainStateActor, AuditReport,
+        RegisterDepositRefundCase,
+    },
+    *,
+};
+use core::ops::Mul;
 use near_sdk::AccountId;
 
 pub trait AppchainLifecycleManager {
@@ -22,17 +31,29 @@ pub trait AppchainLifecycleManager {
         fungible_token_metadata: Option<FungibleTokenMetadata>,
         custom_metadata: Option<HashMap<String, String>>,
     );
-    /// Pass auditing of an appchain
-    fn pass_auditing_appchain(&mut self, appchain_id: AppchainId);
-    /// Reject an appchain
-    fn reject_appchain(&mut self, appchain_id: AppchainId);
+    /// Pass auditing of an appchain, with an optional audit report.
+    /// Not allowed when the audit quorum of auditors is enabled.
+    fn pass_auditing_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        audit_report: Option<AuditReport>,
+    );
+    /// Reject an appchain with a reason code and the detail of the reason
+    fn reject_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        reason_code: AppchainRejectionCode,
+        reason: String,
+    );
     /// Start voting of an appchain
     fn start_voting_appchain(&mut self, appchain_id: AppchainId, dao_proposal_url: String);
     /// Change the state of a given appchain to 'booting',
     /// create sub-account for the appchain and transfer the initial deposit
     fn start_booting_appchain(&mut self, appchain_id: AppchainId);
-    /// Remove an appchain from registry
-    fn remove_appchain(&mut self, appchain_id: AppchainId);
+    /// Remove an appchain from registry.
+    /// If `teardown_anchor` is true, the anchor account of the appchain is torn down first,
+    /// otherwise it needs to be removed manually.
+    fn remove_appchain(&mut self, appchain_id: AppchainId, teardown_anchor: bool);
 }
 
 #[near_bindgen]
@@ -58,6 +79,7 @@ impl AppchainLifecycleManager for AppchainRegistry {
         custom_metadata: Option<HashMap<String, String>>,
     ) {
         self.assert_appchain_lifecycle_manager();
+        let storage_usage_before = env::storage_usage();
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
         let mut metadata = appchain_basedata.metadata();
         if let Some(description) = description {
@@ -180,8 +202,12 @@ impl AppchainLifecycleManager for AppchainRegistry {
             metadata.custom_metadata = custom_metadata;
         }
         appchain_basedata.set_metadata(metadata);
-        self.appchain_basedatas
-            .insert(&appchain_id, &appchain_basedata);
+        if let Err(message) = self.internal_save_appchain_with_storage_charged(
+            &mut appchain_basedata,
+            storage_usage_before,
+        ) {
+            env::panic_str(message.as_str());
+        }
         log!(
             "The metadata of appchain '{}' is updated by '{}'.",
             appchain_basedata.id(),
@@ -189,29 +215,64 @@ impl AppchainLifecycleManager for AppchainRegistry {
         );
     }
     //
-    fn pass_auditing_appchain(&mut self, appchain_id: AppchainId) {
+    fn pass_auditing_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        audit_report: Option<AuditReport>,
+    ) {
         self.assert_appchain_lifecycle_manager();
-        self.assert_appchain_state(&appchain_id, [AppchainState::Registered].to_vec());
+        assert!(
+            self.registry_settings.get().unwrap().audit_threshold == 0,
+            "Appchains can only be audited by auditors when audit quorum is enabled."
+        );
+        if let Some(audit_report) = audit_report.as_ref() {
+            assert!(
+                !audit_report.report_url.trim().is_empty(),
+                "The url of audit report can not be blank."
+            );
+            assert!(
+                !audit_report.content_hash.trim().is_empty(),
+                "The content hash of audit report can not be blank."
+            );
+        }
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        appchain_basedata.set_state(AppchainState::Audited);
+        self.change_appchain_state(
+            &mut appchain_basedata,
+            AppchainState::Audited,
+            AppchainStateActor::AppchainLifecycleManager,
+            None,
+        );
+        appchain_basedata.audit_report = audit_report;
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         log_appchain_state(&appchain_basedata);
     }
     //
-    fn reject_appchain(&mut self, appchain_id: AppchainId) {
+    fn reject_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        reason_code: AppchainRejectionCode,
+        reason: String,
+    ) {
         self.assert_appchain_lifecycle_manager();
-        self.assert_appchain_state(
-            &appchain_id,
-            [
-                AppchainState::Registered,
-                AppchainState::Audited,
-                AppchainState::Voting,
-            ]
-            .to_vec(),
+        assert!(
+            !reason.trim().is_empty(),
+            "The reason of rejection can not be blank."
         );
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        appchain_basedata.set_state(AppchainState::Closed);
+        self.change_appchain_state(
+            &mut appchain_basedata,
+            AppchainState::Closed,
+            AppchainStateActor::AppchainLifecycleManager,
+            Some(reason.clone()),
+        );
+        appchain_basedata.rejection = Some(AppchainRejection {
+            code: reason_code,
+            reason,
+            rejected_by: env::predecessor_account_id(),
+        });
+        appchain_basedata.set_register_deposit_refund_case(RegisterDepositRefundCase::Rejected);
+        self.internal_settle_register_deposit(&mut appchain_basedata);
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         log_appchain_state(&appchain_basedata);
@@ -219,14 +280,31 @@ impl AppchainLifecycleManager for AppchainRegistry {
     //
     fn start_voting_appchain(&mut self, appchain_id: AppchainId, dao_proposal_url: String) {
         self.assert_appchain_lifecycle_manager();
-        self.assert_appchain_state(&appchain_id, [AppchainState::Audited].to_vec());
         assert!(
             !dao_proposal_url.trim().is_empty(),
             "The DAO proposal url can not be blank."
         );
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        appchain_basedata.set_state(AppchainState::Voting);
+        self.change_appchain_state(
+            &mut appchain_basedata,
+            AppchainState::Voting,
+            AppchainStateActor::AppchainLifecycleManager,
+            None,
+        );
         appchain_basedata.dao_proposal_url = Some(dao_proposal_url);
+        let voting_duration = self
+            .registry_settings
+            .get()
+            .unwrap()
+            .voting_duration_in_seconds
+            .0;
+        appchain_basedata.voting_deadline = match voting_duration {
+            0 => None,
+            _ => Some(
+                env::block_timestamp()
+                    .saturating_add(voting_duration.saturating_mul(NANO_SECONDS_MULTIPLE)),
+            ),
+        };
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         log_appchain_state(&appchain_basedata);
@@ -234,13 +312,11 @@ impl AppchainLifecycleManager for AppchainRegistry {
     //
     fn start_booting_appchain(&mut self, appchain_id: AppchainId) {
         self.assert_octopus_council();
-        self.assert_appchain_state(&appchain_id, [AppchainState::Voting].to_vec());
-        self.internal_start_booting_appchain(appchain_id);
+        self.internal_start_booting_appchain(appchain_id, AppchainStateActor::OctopusCouncil);
     }
     //
-    fn remove_appchain(&mut self, appchain_id: AppchainId) {
+    fn remove_appchain(&mut self, appchain_id: AppchainId, teardown_anchor: bool) {
         self.assert_appchain_lifecycle_manager();
-        self.assert_appchain_state(&appchain_id, [AppchainState::Closed].to_vec());
         let appchain_basedata = self.get_appchain_basedata(&appchain_id);
         assert!(
             appchain_basedata.upvote_deposit() == 0,
@@ -250,16 +326,7 @@ impl AppchainLifecycleManager for AppchainRegistry {
             appchain_basedata.downvote_deposit() == 0,
             "The appchain still has downvote deposit(s)."
         );
-        if !appchain_basedata.anchor().is_none() {
-            let anchor_account_id = format!("{}.{}", &appchain_id, env::current_account_id());
-            log!(
-                "The anchor contract '{}' of appchain '{}' needs to be removed manually.",
-                &anchor_account_id,
-                &appchain_id
-            );
-        }
-        self.internal_remove_appchain(&appchain_id);
-        log!("Appchain '{}' is removed from registry.", &appchain_id);
+        self.internal_remove_appchain_with_anchor(&appchain_id, teardown_anchor);
     }
 }
 
@@ -272,23 +339,196 @@ fn log_appchain_state(appchain_basedata: &AppchainBasedata) {
 }
 
 impl AppchainRegistry {
-    pub fn internal_start_booting_appchain(&mut self, appchain_id: AppchainId) {
+    pub fn internal_start_booting_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        actor: AppchainStateActor,
+    ) {
         let sub_account_id =
             AccountId::try_from(format!("{}.{}", &appchain_id, env::current_account_id())).unwrap();
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        let appchain_type = appchain_basedata.metadata().appchain_type;
+        let anchor_template = self.current_anchor_template_of(&appchain_type);
+        self.change_appchain_state(&mut appchain_basedata, AppchainState::Booting, actor, None);
         appchain_basedata.set_anchor_account(sub_account_id.clone());
-        appchain_basedata.set_state(AppchainState::Booting);
+        if let Some(template) = anchor_template.as_ref() {
+            appchain_basedata.set_anchor_deployment_status(AnchorDeploymentStatus::Deploying);
+            appchain_basedata.set_anchor_template_version(template.version.clone());
+        }
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         log_appchain_state(&appchain_basedata);
         //
-        let init_deposit = match appchain_basedata.metadata().appchain_type {
+        let init_deposit = match appchain_type {
             AppchainType::Substrate(_) => SUBSTRATE_ANCHOR_INIT_BALANCE,
             AppchainType::Cosmos => IBC_ANCHOR_INIT_BALANCE,
         };
-        Promise::new(sub_account_id)
+        let promise = Promise::new(sub_account_id)
             .create_account()
             .transfer(init_deposit)
             .add_full_access_key(self.owner_pk.clone());
+        if let Some(template) = anchor_template {
+            let anchor_code = env::storage_read(
+                &StorageKey::AnchorTemplateWasm(AnchorTemplate::key_of(
+                    &template.appchain_type,
+                    &template.version,
+                ))
+                .into_bytes(),
+            )
+            .unwrap();
+            promise
+                .deploy_contract(anchor_code)
+                .function_call(
+                    "new".to_string(),
+                    self.anchor_init_args(&appchain_basedata),
+                    0,
+                    Gas::ONE_TERA.mul(T_GAS_FOR_ANCHOR_INITIALIZATION),
+                )
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                        .with_unused_gas_weight(0)
+                        .resolve_anchor_deployment(appchain_id),
+                );
+        }
+    }
+    /// Remove an appchain from registry. If `teardown_anchor` is true and the anchor account
+    /// of the appchain exists, it is torn down (with this contract as beneficiary) first,
+    /// and the appchain is removed in the callback.
+    pub fn internal_remove_appchain_with_anchor(
+        &mut self,
+        appchain_id: &AppchainId,
+        teardown_anchor: bool,
+    ) {
+        let mut appchain_basedata = self.get_appchain_basedata(appchain_id);
+        if let Err(reason) = self.check_appchain_removable(&appchain_basedata) {
+            panic!("{}", reason);
+        }
+        assert!(
+            appchain_basedata.anchor_teardown_status != Some(AnchorTeardownStatus::TearingDown),
+            "The anchor of appchain '{}' is being torn down.",
+            appchain_id
+        );
+        // The anchor account does NOT exist if it is already torn down, or if the deployment
+        // failed at booting. In the latter case, the whole batch of actions (including the
+        // creation of the account and the transfer of its initial balance) is reverted,
+        // so the initial balance is already refunded to this contract.
+        let anchor = match appchain_basedata.anchor() {
+            Some(anchor)
+                if appchain_basedata.anchor_teardown_status
+                    != Some(AnchorTeardownStatus::TornDown)
+                    && appchain_basedata.anchor_deployment_status
+                        != Some(AnchorDeploymentStatus::Failed) =>
+            {
+                anchor
+            }
+            _ => {
+                self.internal_remove_appchain(appchain_id);
+                log!("Appchain '{}' is removed from registry.", appchain_id);
+                return;
+            }
+        };
+        if !teardown_anchor {
+            log!(
+                "The anchor account '{}' of appchain '{}' needs to be removed manually.",
+                &anchor,
+                appchain_id
+            );
+            self.internal_remove_appchain(appchain_id);
+            log!("Appchain '{}' is removed from registry.", appchain_id);
+            return;
+        }
+        assert_eq!(
+            appchain_basedata.anchor_deployment_status,
+            Some(AnchorDeploymentStatus::Deployed),
+            "The anchor contract of appchain '{}' is NOT deployed by registry, it can NOT be torn down.",
+            appchain_id
+        );
+        appchain_basedata.set_anchor_teardown_status(AnchorTeardownStatus::TearingDown);
+        self.appchain_basedatas
+            .insert(appchain_id, &appchain_basedata);
+        Promise::new(anchor.clone())
+            .function_call(
+                "teardown".to_string(),
+                serde_json::json!({ "beneficiary": env::current_account_id() })
+                    .to_string()
+                    .into_bytes(),
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_ANCHOR_TEARDOWN),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_anchor_teardown(appchain_id.clone()),
+            );
+        log!(
+            "Tearing down anchor '{}' of appchain '{}'.",
+            &anchor,
+            appchain_id
+        );
+    }
+    /// Check whether an appchain can be removed from registry, return the reason if not
+    pub fn check_appchain_removable(
+        &self,
+        appchain_basedata: &AppchainBasedata,
+    ) -> Result<(), String> {
+        if !appchain_basedata.state().eq(&AppchainState::Closed) {
+            return Err(format!(
+                "Appchain '{}' is NOT closed.",
+                appchain_basedata.id()
+            ));
+        }
+        if self.appchain_voters.get(appchain_basedata.id()).is_some() {
+            return Err(format!(
+                "The vote deposits of appchain '{}' should be refunded first.",
+                appchain_basedata.id()
+            ));
+        }
+        if appchain_basedata.pending_vote_deposit_refunds > 0 {
+            return Err(format!(
+                "The vote deposit refunds (or withdrawals) of appchain '{}' are in flight, try again later.",
+                appchain_basedata.id()
+            ));
+        }
+        // Removing an id from the set of appchain ids moves the last id to its position,
+        // which will break the cursor of an in-progress round of voting score counting.
+        if self
+            .voting_score_counting_status
+            .next_appchain_index
+            .is_some()
+        {
+            return Err(
+                "A round of voting score counting is in progress, try again later.".to_string(),
+            );
+        }
+        Ok(())
+    }
+    /// Build the arguments of the initialization function of appchain anchor,
+    /// according to the type of the appchain
+    fn anchor_init_args(&self, appchain_basedata: &AppchainBasedata) -> Vec<u8> {
+        let metadata = appchain_basedata.metadata();
+        match metadata.appchain_type {
+            AppchainType::Substrate(template_type) => serde_json::json!({
+                "appchain_id": appchain_basedata.id(),
+                "appchain_template_type": template_type,
+                "appchain_registry": env::current_account_id(),
+                "oct_token": self.oct_token,
+                "evm_chain_id": appchain_basedata.evm_chain_id,
+                "premined_wrapped_appchain_token_beneficiary": metadata.premined_wrapped_appchain_token_beneficiary,
+                "premined_wrapped_appchain_token": metadata.premined_wrapped_appchain_token,
+                "initial_supply_of_wrapped_appchain_token": metadata.initial_supply_of_wrapped_appchain_token,
+                "ido_amount_of_wrapped_appchain_token": metadata.ido_amount_of_wrapped_appchain_token,
+                "initial_era_reward": metadata.initial_era_reward,
+                "fungible_token_metadata": metadata.fungible_token_metadata,
+            }),
+            AppchainType::Cosmos => serde_json::json!({
+                "appchain_id": appchain_basedata.id(),
+                "appchain_registry": env::current_account_id(),
+                "oct_token": self.oct_token,
+            }),
+        }
+        .to_string()
+        .into_bytes()
     }
 }
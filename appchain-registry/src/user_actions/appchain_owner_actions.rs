@@ -1,4 +1,8 @@
-use crate::*;
+use crate::{
+    types::{AppchainStateActor, RegisterDepositAsset, RegisterDepositRefundCase},
+    *,
+};
+use core::ops::Mul;
 use near_sdk::{near_bindgen, AccountId};
 
 /// The actions which the owner of an appchain can perform
@@ -9,6 +13,13 @@ pub trait AppchainOwnerActions {
     /// Can be called by the appchain owner while the appchain state is 'registered' or 'audited'.
     /// After the withdrawal, the appchain's state will change to 'Closed'.
     fn withdraw_appchain(&mut self, appchain_id: AppchainId);
+    /// Claim the refund of register deposit of an appchain.
+    /// The refund amount is decided by the register deposit refund policy in registry settings
+    /// at the time the appchain is closed or goes live, and can only be claimed once.
+    fn claim_register_deposit_refund(&mut self, appchain_id: AppchainId);
+    /// Claim the unclaimed register deposit refunds (in the given asset) of the removed
+    /// appchains which were owned by the caller.
+    fn claim_register_deposit_refunds_of_removed_appchains(&mut self, asset: RegisterDepositAsset);
 }
 
 #[near_bindgen]
@@ -17,6 +28,15 @@ impl AppchainOwnerActions for AppchainRegistry {
     fn transfer_appchain_ownership(&mut self, appchain_id: AppchainId, new_owner: AccountId) {
         self.assert_appchain_owner(&appchain_id);
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        if let Err(message) =
+            self.internal_charge_storage_usage(&new_owner, appchain_basedata.storage_usage)
+        {
+            env::panic_str(message.as_str());
+        }
+        self.internal_release_storage_usage(
+            &appchain_basedata.owner(),
+            appchain_basedata.storage_usage,
+        );
         appchain_basedata.change_owner(new_owner);
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
@@ -30,14 +50,18 @@ impl AppchainOwnerActions for AppchainRegistry {
     fn withdraw_appchain(&mut self, appchain_id: AppchainId) {
         self.assert_appchain_owner(&appchain_id);
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        assert!(
-            appchain_basedata.state().eq(&AppchainState::Registered)
-                || appchain_basedata.state().eq(&AppchainState::Audited),
-            "Appchain state must be '{}' or '{}'.",
-            AppchainState::Registered,
-            AppchainState::Audited,
+        let refund_case = match appchain_basedata.state() {
+            AppchainState::Registered => RegisterDepositRefundCase::WithdrawnWhileRegistered,
+            _ => RegisterDepositRefundCase::WithdrawnAfterAudit,
+        };
+        self.change_appchain_state(
+            &mut appchain_basedata,
+            AppchainState::Closed,
+            AppchainStateActor::AppchainOwner,
+            None,
         );
-        appchain_basedata.appchain_state = AppchainState::Closed;
+        appchain_basedata.set_register_deposit_refund_case(refund_case);
+        self.internal_settle_register_deposit(&mut appchain_basedata);
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         log!(
@@ -46,4 +70,62 @@ impl AppchainOwnerActions for AppchainRegistry {
             appchain_basedata.owner()
         );
     }
+    //
+    fn claim_register_deposit_refund(&mut self, appchain_id: AppchainId) {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_appchain_owner(&appchain_id);
+        let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        assert!(
+            appchain_basedata.refunded_register_deposit.is_none(),
+            "The register deposit of appchain '{}' is already refunded.",
+            appchain_id
+        );
+        let refund_amount = appchain_basedata
+            .refundable_register_deposit
+            .expect("The register deposit of the appchain is not refundable.");
+        assert!(
+            refund_amount > 0,
+            "Nothing to refund for appchain '{}'.",
+            appchain_id
+        );
+        appchain_basedata.set_refunded_register_deposit(Some(refund_amount));
+        let asset = appchain_basedata.register_deposit_asset();
+        let ledger = self.registry_treasury.ledger_of_mut(&asset);
+        ledger.reserved_register_deposit_refunds =
+            (ledger.reserved_register_deposit_refunds.0 - refund_amount).into();
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        self.internal_transfer_asset(&asset, &appchain_basedata.owner(), refund_amount)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_register_deposit_refund(
+                        appchain_id,
+                        appchain_basedata.owner(),
+                        asset,
+                        refund_amount.into(),
+                    ),
+            );
+    }
+    //
+    fn claim_register_deposit_refunds_of_removed_appchains(&mut self, asset: RegisterDepositAsset) {
+        self.assert_asset_transfer_is_not_paused();
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .claimable_register_deposits
+            .remove(&(account_id.clone(), asset.clone()))
+            .unwrap_or(0);
+        assert!(amount > 0, "Nothing to claim.");
+        let ledger = self.registry_treasury.ledger_of_mut(&asset);
+        ledger.reserved_register_deposit_refunds =
+            (ledger.reserved_register_deposit_refunds.0 - amount).into();
+        self.internal_transfer_asset(&asset, &account_id, amount)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_claimable_register_deposit_refund(account_id, asset, amount.into()),
+            );
+    }
 }
@@ -0,0 +1,110 @@
+use crate::{
+    types::{
+        AppchainRejection, AppchainRejectionCode, AppchainStateActor, AuditAttestation,
+        AuditReport, RegisterDepositRefundCase,
+    },
+    *,
+};
+
+/// The actions of auditors of appchain registry
+pub trait AuditorActions {
+    /// Submit an attestation (approve or reject) on a registered appchain.
+    ///
+    /// The appchain becomes 'audited' once the approvals reach the audit threshold,
+    /// or becomes 'closed' once the rejections make it impossible to reach the threshold.
+    fn submit_audit_attestation(
+        &mut self,
+        appchain_id: AppchainId,
+        approved: bool,
+        audit_report: Option<AuditReport>,
+        comment: String,
+    );
+}
+
+#[near_bindgen]
+impl AuditorActions for AppchainRegistry {
+    //
+    fn submit_audit_attestation(
+        &mut self,
+        appchain_id: AppchainId,
+        approved: bool,
+        audit_report: Option<AuditReport>,
+        comment: String,
+    ) {
+        self.assert_auditor();
+        let audit_threshold = self.registry_settings.get().unwrap().audit_threshold as usize;
+        assert!(audit_threshold > 0, "The audit quorum is not enabled.");
+        self.assert_appchain_state(&appchain_id, [AppchainState::Registered].to_vec());
+        let auditor = env::predecessor_account_id();
+        let mut attestations = self
+            .appchain_audit_attestations
+            .get(&appchain_id)
+            .unwrap_or_default();
+        assert!(
+            !attestations
+                .iter()
+                .any(|attestation| attestation.auditor.eq(&auditor)),
+            "The auditor has already submitted attestation for appchain '{}'.",
+            appchain_id
+        );
+        attestations.push(AuditAttestation {
+            auditor: auditor.clone(),
+            approved,
+            audit_report,
+            comment,
+            submitted_at: env::block_timestamp().into(),
+        });
+        self.appchain_audit_attestations
+            .insert(&appchain_id, &attestations);
+        //
+        // Only the attestations of current auditors are counted.
+        let auditors = self.registry_roles.get().unwrap().auditors;
+        let counted_attestations: Vec<&AuditAttestation> = attestations
+            .iter()
+            .filter(|attestation| auditors.contains(&attestation.auditor))
+            .collect();
+        let approvals = counted_attestations
+            .iter()
+            .filter(|attestation| attestation.approved)
+            .count();
+        let rejections = counted_attestations.len() - approvals;
+        let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        if approvals >= audit_threshold {
+            self.change_appchain_state(
+                &mut appchain_basedata,
+                AppchainState::Audited,
+                AppchainStateActor::AuditorQuorum,
+                None,
+            );
+            appchain_basedata.audit_report = counted_attestations
+                .iter()
+                .rev()
+                .filter(|attestation| attestation.approved)
+                .find_map(|attestation| attestation.audit_report.clone());
+        } else if auditors.len() - rejections < audit_threshold {
+            let reason = format!("Rejected by {} of {} auditors.", rejections, auditors.len());
+            self.change_appchain_state(
+                &mut appchain_basedata,
+                AppchainState::Closed,
+                AppchainStateActor::AuditorQuorum,
+                Some(reason.clone()),
+            );
+            appchain_basedata.rejection = Some(AppchainRejection {
+                code: AppchainRejectionCode::FailedAuditing,
+                reason,
+                rejected_by: auditor,
+            });
+            appchain_basedata.set_register_deposit_refund_case(RegisterDepositRefundCase::Rejected);
+            self.internal_settle_register_deposit(&mut appchain_basedata);
+        } else {
+            return;
+        }
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        log!(
+            "Appchain '{}' is '{}'.",
+            appchain_basedata.id(),
+            appchain_basedata.appchain_state
+        );
+    }
+}
@@ -1,6 +1,10 @@
+mod anchor_template_manager;
 mod appchain_lifecycle;
 mod appchain_owner_actions;
+mod auditor_actions;
+mod permissionless_actions;
 mod registry_roles;
 mod registry_settings;
 mod sudo_actions;
+mod treasury_actions;
 mod voter_actions;
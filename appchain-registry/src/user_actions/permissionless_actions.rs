@@ -0,0 +1,168 @@
+use crate::{
+    types::{
+        AppchainRejection, AppchainRejectionCode, AppchainStateActor, RegisterDepositRefundCase,
+        VotingExpiryPolicy,
+    },
+    *,
+};
+
+/// The actions which can be performed by any account
+pub trait PermissionlessActions {
+    /// Count voting score of all appchains in 'voting' state.
+    ///
+    /// A new counting round can only be started after the voting score counting interval
+    /// since the start of the last round. The appchains are processed in batches,
+    /// this function should be called repeatedly until it returns `Ok`.
+    ///
+    /// Appchains can NOT be removed while a round is in progress. New appchains are
+    /// appended to the end of the set of appchain ids, so they do NOT affect the cursor.
+    fn count_voting_score(&mut self) -> MultiTxsOperationProcessingResult;
+    /// Refund the upvote and downvote deposits of at most `limit` voters of a closed appchain.
+    ///
+    /// This function should be called repeatedly until it returns `Ok`.
+    fn refund_votes_of(
+        &mut self,
+        appchain_id: AppchainId,
+        limit: u32,
+    ) -> MultiTxsOperationProcessingResult;
+    /// Expire the voting of an appchain whose voting deadline has passed.
+    ///
+    /// The appchain goes to 'closed' or back to 'audited', depending on the voting expiry policy.
+    fn expire_voting(&mut self, appchain_id: AppchainId);
+}
+
+#[near_bindgen]
+impl PermissionlessActions for AppchainRegistry {
+    //
+    fn count_voting_score(&mut self) -> MultiTxsOperationProcessingResult {
+        let registry_settings = self.registry_settings.get().unwrap();
+        let mut counting_status = self.voting_score_counting_status.clone();
+        let mut next_index = match counting_status.next_appchain_index {
+            Some(index) => index.0,
+            None => {
+                assert!(
+                    env::block_timestamp()
+                        >= counting_status.round_start_time.0
+                            + registry_settings
+                                .voting_score_counting_interval_in_seconds
+                                .0
+                                * NANO_SECONDS_MULTIPLE,
+                    "Voting score can only be counted once in an interval."
+                );
+                counting_status.round_start_time = env::block_timestamp().into();
+                0
+            }
+        };
+        let appchain_ids = self.appchain_ids.as_vector();
+        while next_index < appchain_ids.len()
+            && env::used_gas() < Gas::ONE_TERA * T_GAS_CAP_FOR_MULTI_TXS_PROCESSING
+        {
+            let appchain_id = appchain_ids.get(next_index).unwrap();
+            let appchain_basedata = self.get_appchain_basedata(&appchain_id);
+            if appchain_basedata.state().eq(&AppchainState::Voting) {
+                appchain_basedata
+                    .reduce_voting_score_by_percent(registry_settings.voting_score_decay_percent);
+                appchain_basedata.count_voting_score();
+            }
+            next_index += 1;
+        }
+        let result = if next_index < appchain_ids.len() {
+            counting_status.next_appchain_index = Some(next_index.into());
+            MultiTxsOperationProcessingResult::NeedMoreGas
+        } else {
+            counting_status.next_appchain_index = None;
+            counting_status.last_round_end_time = env::block_timestamp().into();
+            log!(
+                "Voting score counting round started at '{}' is finished.",
+                counting_status.round_start_time.0
+            );
+            MultiTxsOperationProcessingResult::Ok
+        };
+        self.voting_score_counting_status = counting_status;
+        result
+    }
+    //
+    fn refund_votes_of(
+        &mut self,
+        appchain_id: AppchainId,
+        limit: u32,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_appchain_state(&appchain_id, [AppchainState::Closed].to_vec());
+        assert!(limit > 0, "The limit should NOT be 0.");
+        let voters: Vec<AccountId> = match self.appchain_voters.get(&appchain_id) {
+            Some(voters) => voters.iter().take(limit as usize).collect(),
+            None => return MultiTxsOperationProcessingResult::Ok,
+        };
+        for voter in voters.iter() {
+            if env::prepaid_gas() - env::used_gas()
+                < Gas::ONE_TERA * T_GAS_FOR_REFUNDING_VOTE_DEPOSITS_OF_A_VOTER
+            {
+                break;
+            }
+            self.internal_refund_vote_deposits_of(&appchain_id, voter);
+        }
+        if self.appchain_voters.get(&appchain_id).is_some() {
+            MultiTxsOperationProcessingResult::NeedMoreGas
+        } else {
+            log!(
+                "The vote deposits of all voters of appchain '{}' are refunded.",
+                &appchain_id
+            );
+            MultiTxsOperationProcessingResult::Ok
+        }
+    }
+    //
+    fn expire_voting(&mut self, appchain_id: AppchainId) {
+        let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
+        assert_eq!(
+            appchain_basedata.state(),
+            AppchainState::Voting,
+            "Appchain '{}' is not in voting.",
+            appchain_id
+        );
+        let voting_deadline = appchain_basedata
+            .voting_deadline
+            .expect("The voting of the appchain has no deadline.");
+        assert!(
+            env::block_timestamp() >= voting_deadline,
+            "The voting of the appchain has not expired."
+        );
+        let reason = "The voting period expired.".to_string();
+        match self.registry_settings.get().unwrap().voting_expiry_policy {
+            VotingExpiryPolicy::Close => {
+                self.change_appchain_state(
+                    &mut appchain_basedata,
+                    AppchainState::Closed,
+                    AppchainStateActor::VotingExpiry,
+                    Some(reason.clone()),
+                );
+                appchain_basedata.rejection = Some(AppchainRejection {
+                    code: AppchainRejectionCode::FailedVoting,
+                    reason,
+                    rejected_by: env::predecessor_account_id(),
+                });
+                appchain_basedata
+                    .set_register_deposit_refund_case(RegisterDepositRefundCase::Rejected);
+                self.internal_settle_register_deposit(&mut appchain_basedata);
+            }
+            VotingExpiryPolicy::BackToAudited => {
+                self.change_appchain_state(
+                    &mut appchain_basedata,
+                    AppchainState::Audited,
+                    AppchainStateActor::VotingExpiry,
+                    Some(reason),
+                );
+                appchain_basedata.voting_deadline = None;
+            }
+        }
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        log!(
+            "The voting of appchain '{}' is expired. Appchain '{}' is '{}'.",
+            appchain_id,
+            appchain_id,
+            appchain_basedata.appchain_state
+        );
+    }
+}
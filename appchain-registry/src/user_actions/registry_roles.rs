@@ -6,6 +6,7 @@ impl Default for RegistryRoles {
             appchain_lifecycle_manager: env::signer_account_id(),
             registry_settings_manager: env::signer_account_id(),
             octopus_council: None,
+            auditors: Vec::new(),
         }
     }
 }
@@ -56,4 +57,33 @@ impl AppchainRegistry {
         registry_roles.octopus_council = Some(account);
         self.registry_roles.set(&registry_roles);
     }
+    //
+    pub fn add_auditor(&mut self, account: AccountId) {
+        self.assert_owner();
+        let mut registry_roles = self.registry_roles.get().unwrap();
+        assert!(
+            !registry_roles.auditors.contains(&account),
+            "The account is already an auditor."
+        );
+        registry_roles.auditors.push(account);
+        self.registry_roles.set(&registry_roles);
+    }
+    //
+    pub fn remove_auditor(&mut self, account: AccountId) {
+        self.assert_owner();
+        let mut registry_roles = self.registry_roles.get().unwrap();
+        assert!(
+            registry_roles.auditors.contains(&account),
+            "The account is not an auditor."
+        );
+        assert!(
+            (self.registry_settings.get().unwrap().audit_threshold as usize)
+                < registry_roles.auditors.len(),
+            "The number of auditors can not be less than the audit threshold."
+        );
+        registry_roles
+            .auditors
+            .retain(|auditor| !auditor.eq(&account));
+        self.registry_roles.set(&registry_roles);
+    }
 }
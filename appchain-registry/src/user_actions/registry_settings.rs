@@ -1,14 +1,55 @@
-use crate::*;
+use crate::{
+    types::{
+        RegisterDepositAsset, RegisterDepositAssetSetting, RegisterDepositRefundPolicy,
+        VotingExpiryPolicy,
+    },
+    *,
+};
 
 pub trait RegistrySettingsManager {
     /// Change the value of minimum register deposit
     fn change_minimum_register_deposit(&mut self, value: U128);
+    /// Change the interval (in seconds) between two rounds of counting voting score
+    fn change_voting_score_counting_interval(&mut self, value: U64);
+    /// Change the percent of voting score decay in each counting round
+    fn change_voting_score_decay_percent(&mut self, value: u16);
+    /// Change the refund policy of register deposit
+    fn change_register_deposit_refund_policy(&mut self, value: RegisterDepositRefundPolicy);
+    /// Add or update an accepted asset (other than OCT token) for register deposit,
+    /// with its minimum register deposit
+    fn set_register_deposit_asset(
+        &mut self,
+        asset: RegisterDepositAsset,
+        minimum_register_deposit: U128,
+    );
+    /// Remove an accepted asset (other than OCT token) for register deposit
+    fn remove_register_deposit_asset(&mut self, asset: RegisterDepositAsset);
+    /// Change the duration (in seconds) of the voting period of appchains,
+    /// 0 for no deadline
+    fn change_voting_duration(&mut self, value: U64);
+    /// Change the policy for the appchains whose voting period expires
+    fn change_voting_expiry_policy(&mut self, value: VotingExpiryPolicy);
+    /// Change the number of approvals of auditors needed for an appchain to pass auditing
+    fn change_audit_threshold(&mut self, value: u16);
+    /// Change the stale threshold (in seconds) of the syncs from appchain anchors,
+    /// 0 for disabling the staleness detection
+    fn change_anchor_sync_stale_threshold(&mut self, value: U64);
 }
 
 impl Default for RegistrySettings {
     fn default() -> Self {
         Self {
             minimum_register_deposit: U128::from(DEFAULT_REGISTER_DEPOSIT * OCT_DECIMALS_BASE),
+            voting_score_counting_interval_in_seconds: U64::from(
+                DEFAULT_VOTING_SCORE_COUNTING_INTERVAL,
+            ),
+            voting_score_decay_percent: DEFAULT_VOTING_SCORE_DECAY_PERCENT,
+            register_deposit_refund_policy: RegisterDepositRefundPolicy::default(),
+            other_register_deposit_assets: Vec::new(),
+            voting_duration_in_seconds: U64::from(DEFAULT_VOTING_DURATION),
+            voting_expiry_policy: VotingExpiryPolicy::Close,
+            audit_threshold: 0,
+            anchor_sync_stale_threshold_in_seconds: U64::from(DEFAULT_ANCHOR_SYNC_STALE_THRESHOLD),
         }
     }
 }
@@ -23,4 +64,124 @@ impl RegistrySettingsManager for AppchainRegistry {
         registry_settings.minimum_register_deposit = value;
         self.registry_settings.set(&registry_settings);
     }
+    //
+    fn change_voting_score_counting_interval(&mut self, value: U64) {
+        self.assert_registry_settings_manager();
+        assert!(
+            value.0 > 0,
+            "The voting score counting interval should NOT be 0."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.voting_score_counting_interval_in_seconds = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_voting_score_decay_percent(&mut self, value: u16) {
+        self.assert_registry_settings_manager();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.voting_score_decay_percent = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_register_deposit_refund_policy(&mut self, value: RegisterDepositRefundPolicy) {
+        self.assert_registry_settings_manager();
+        assert!(
+            value.withdrawn_while_registered_percent <= 100
+                && value.withdrawn_after_audit_percent <= 100
+                && value.rejected_percent <= 100
+                && value.went_live_percent <= 100,
+            "Invalid percent value."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.register_deposit_refund_policy = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn set_register_deposit_asset(
+        &mut self,
+        asset: RegisterDepositAsset,
+        minimum_register_deposit: U128,
+    ) {
+        self.assert_registry_settings_manager();
+        assert!(
+            !asset.eq(&RegisterDepositAsset::FungibleToken(self.oct_token.clone())),
+            "Use 'change_minimum_register_deposit' for OCT token."
+        );
+        assert!(
+            minimum_register_deposit.0 > 0,
+            "The minimum register deposit should NOT be 0."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        match registry_settings
+            .other_register_deposit_assets
+            .iter_mut()
+            .find(|setting| setting.asset.eq(&asset))
+        {
+            Some(setting) => setting.minimum_register_deposit = minimum_register_deposit,
+            None => {
+                registry_settings
+                    .other_register_deposit_assets
+                    .push(RegisterDepositAssetSetting {
+                        asset,
+                        minimum_register_deposit,
+                    })
+            }
+        }
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn remove_register_deposit_asset(&mut self, asset: RegisterDepositAsset) {
+        self.assert_registry_settings_manager();
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        let count = registry_settings.other_register_deposit_assets.len();
+        registry_settings
+            .other_register_deposit_assets
+            .retain(|setting| !setting.asset.eq(&asset));
+        assert!(
+            registry_settings.other_register_deposit_assets.len() < count,
+            "The asset is not accepted for register deposit."
+        );
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_voting_duration(&mut self, value: U64) {
+        self.assert_registry_settings_manager();
+        assert!(
+            value.0.checked_mul(NANO_SECONDS_MULTIPLE).is_some(),
+            "The duration is too large."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.voting_duration_in_seconds = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_voting_expiry_policy(&mut self, value: VotingExpiryPolicy) {
+        self.assert_registry_settings_manager();
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.voting_expiry_policy = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_audit_threshold(&mut self, value: u16) {
+        self.assert_registry_settings_manager();
+        assert!(
+            value as usize <= self.registry_roles.get().unwrap().auditors.len(),
+            "The audit threshold can not be greater than the number of auditors."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.audit_threshold = value;
+        self.registry_settings.set(&registry_settings);
+    }
+    //
+    fn change_anchor_sync_stale_threshold(&mut self, value: U64) {
+        self.assert_registry_settings_manager();
+        assert!(
+            value.0.checked_mul(NANO_SECONDS_MULTIPLE).is_some(),
+            "The threshold is too large."
+        );
+        let mut registry_settings = self.registry_settings.get().unwrap();
+        registry_settings.anchor_sync_stale_threshold_in_seconds = value;
+        self.registry_settings.set(&registry_settings);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{types::AppchainStateActor, *};
 use core::{convert::TryFrom, str::FromStr};
 
 pub trait SudoActions {
@@ -6,16 +6,39 @@ pub trait SudoActions {
     fn set_owner_pk(&mut self, public_key: String);
     /// Create subaccount for a specific appchain.
     fn create_anchor_account(&mut self, appchain_id: AppchainId, appchain_type: AppchainType);
-    /// Force change state of an appchain.
-    fn force_change_appchain_state(&mut self, appchain_id: AppchainId, state: AppchainState);
+    /// Force change state of an appchain, bypassing the transition table.
+    /// The change is recorded as forced in the state history of the appchain.
+    fn force_change_appchain_state(
+        &mut self,
+        appchain_id: AppchainId,
+        state: AppchainState,
+        reason: Option<String>,
+    );
     /// Pause asset transfer in this contract.
     fn pause_asset_transfer(&mut self);
     /// Resume asset transfer in this contract.
     fn resume_asset_transfer(&mut self);
     /// Force remove an appchain.
-    fn force_remove_appchain(&mut self, appchain_id: AppchainId);
+    ///
+    /// If `teardown_anchor` is false, the anchor account of the appchain is NOT torn down,
+    /// and needs to be removed manually.
+    fn force_remove_appchain(&mut self, appchain_id: AppchainId, teardown_anchor: bool);
     /// Force start booting an appchain.
     fn force_start_booting_appchain(&mut self, appchain_id: AppchainId);
+    /// Set the account to receive the fees swept from registry treasury.
+    fn set_treasury_account(&mut self, account_id: AccountId);
+    /// Set the shortest time range between registering an anchor template
+    /// and pushing it to appchain anchors.
+    fn set_contract_code_staging_duration(&mut self, duration_in_seconds: U64);
+    /// Allow octopus council to call a method of appchain anchors, through `call_anchor_function`.
+    /// If `appchain_types` is not specified, the method is allowed for all appchain types.
+    fn allow_anchor_method(
+        &mut self,
+        method_name: String,
+        appchain_types: Option<Vec<AppchainType>>,
+    );
+    /// Remove a method from the allow-list of anchor methods.
+    fn disallow_anchor_method(&mut self, method_name: String);
 }
 
 #[near_bindgen]
@@ -47,7 +70,12 @@ impl SudoActions for AppchainRegistry {
             .add_full_access_key(self.owner_pk.clone());
     }
     //
-    fn force_change_appchain_state(&mut self, appchain_id: AppchainId, new_state: AppchainState) {
+    fn force_change_appchain_state(
+        &mut self,
+        appchain_id: AppchainId,
+        new_state: AppchainState,
+        reason: Option<String>,
+    ) {
         self.assert_owner();
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
         assert!(
@@ -55,7 +83,8 @@ impl SudoActions for AppchainRegistry {
             "Appchain is already in state '{}'",
             &new_state
         );
-        appchain_basedata.set_state(new_state);
+        self.internal_set_appchain_state(&mut appchain_basedata, new_state, reason, true);
+        self.internal_settle_register_deposit(&mut appchain_basedata);
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
     }
@@ -70,28 +99,52 @@ impl SudoActions for AppchainRegistry {
         self.asset_transfer_is_paused = false;
     }
     //
-    fn force_remove_appchain(&mut self, appchain_id: AppchainId) {
+    fn force_remove_appchain(&mut self, appchain_id: AppchainId, teardown_anchor: bool) {
         self.assert_owner();
-        self.assert_appchain_state(&appchain_id, [AppchainState::Closed].to_vec());
-        let appchain_basedata = self.get_appchain_basedata(&appchain_id);
-        if !appchain_basedata.anchor().is_none() {
-            let anchor_account_id = format!("{}.{}", &appchain_id, env::current_account_id());
-            log!(
-                "The anchor contract '{}' of appchain '{}' needs to be removed manually.",
-                &anchor_account_id,
-                &appchain_id
+        self.internal_remove_appchain_with_anchor(&appchain_id, teardown_anchor);
+    }
+    //
+    fn force_start_booting_appchain(&mut self, appchain_id: AppchainId) {
+        self.assert_owner();
+        self.internal_start_booting_appchain(appchain_id, AppchainStateActor::RegistryOwner);
+    }
+    //
+    fn set_treasury_account(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.registry_treasury.treasury_account = Some(account_id);
+    }
+    //
+    fn set_contract_code_staging_duration(&mut self, duration_in_seconds: U64) {
+        self.assert_owner();
+        self.contract_code_staging_duration = duration_in_seconds.0 * NANO_SECONDS_MULTIPLE;
+    }
+    //
+    fn allow_anchor_method(
+        &mut self,
+        method_name: String,
+        appchain_types: Option<Vec<AppchainType>>,
+    ) {
+        self.assert_owner();
+        assert!(
+            !method_name.trim().is_empty(),
+            "The method name should NOT be empty."
+        );
+        if let Some(appchain_types) = appchain_types.as_ref() {
+            assert!(
+                !appchain_types.is_empty(),
+                "The appchain types should NOT be empty."
             );
         }
-        self.internal_remove_appchain(&appchain_id);
-        log!("Appchain '{}' is removed from registry.", &appchain_id);
+        self.allowed_anchor_methods
+            .insert(&method_name, &appchain_types);
     }
     //
-    fn force_start_booting_appchain(&mut self, appchain_id: AppchainId) {
+    fn disallow_anchor_method(&mut self, method_name: String) {
         self.assert_owner();
-        self.assert_appchain_state(
-            &appchain_id,
-            [AppchainState::Audited, AppchainState::Voting].to_vec(),
+        assert!(
+            self.allowed_anchor_methods.remove(&method_name).is_some(),
+            "Method '{}' is not in the allow-list.",
+            method_name
         );
-        self.internal_start_booting_appchain(appchain_id);
     }
 }
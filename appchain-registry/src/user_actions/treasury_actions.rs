@@ -0,0 +1,142 @@
+use crate::{appchain_basedata::AppchainBasedata, types::RegisterDepositAsset, *};
+use core::ops::Mul;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+/// The actions related to the treasury of appchain registry
+pub trait TreasuryActions {
+    /// Sweep the withdrawable fees of the given asset to the treasury account.
+    /// If `amount` is not specified, all of the withdrawable fees will be swept.
+    /// Can be called by the owner or octopus council.
+    fn sweep_treasury(&mut self, asset: RegisterDepositAsset, amount: Option<U128>);
+}
+
+#[near_bindgen]
+impl TreasuryActions for AppchainRegistry {
+    //
+    fn sweep_treasury(&mut self, asset: RegisterDepositAsset, amount: Option<U128>) {
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_owner_or_octopus_council();
+        let treasury_account = self
+            .registry_treasury
+            .treasury_account
+            .clone()
+            .expect("The treasury account is not set.");
+        let ledger = self.registry_treasury.ledger_of_mut(&asset);
+        let withdrawable_fees = ledger.withdrawable_fees.0;
+        let amount = amount.map_or(withdrawable_fees, |amount| amount.0);
+        assert!(amount > 0, "Nothing to sweep.");
+        assert!(
+            amount <= withdrawable_fees,
+            "Not enough withdrawable fees. Available: '{}'",
+            withdrawable_fees
+        );
+        ledger.withdrawable_fees = (withdrawable_fees - amount).into();
+        ledger.swept_fees = (ledger.swept_fees.0 + amount).into();
+        self.internal_transfer_asset(&asset, &treasury_account, amount)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_treasury_sweep(treasury_account, asset, amount.into()),
+            );
+    }
+}
+
+impl AppchainRegistry {
+    /// Settle the register deposit of an appchain which is closed or goes live.
+    ///
+    /// The refundable part (decided by the current refund policy) is reserved for the
+    /// appchain owner, and the rest becomes withdrawable fees of the treasury.
+    pub fn internal_settle_register_deposit(&mut self, appchain_basedata: &mut AppchainBasedata) {
+        if appchain_basedata.refundable_register_deposit.is_some() {
+            return;
+        }
+        match appchain_basedata.state() {
+            AppchainState::Active | AppchainState::Closing | AppchainState::Closed => (),
+            _ => return,
+        }
+        let register_deposit = appchain_basedata.register_deposit();
+        let percent = appchain_basedata
+            .register_deposit_refund_case
+            .as_ref()
+            .map_or(0, |refund_case| {
+                self.registry_settings
+                    .get()
+                    .unwrap()
+                    .register_deposit_refund_policy
+                    .percent_of(refund_case)
+            });
+        let refundable = register_deposit * percent as u128 / 100;
+        appchain_basedata.set_refundable_register_deposit(refundable);
+        let ledger = self
+            .registry_treasury
+            .ledger_of_mut(&appchain_basedata.register_deposit_asset());
+        ledger.pending_register_deposits =
+            (ledger.pending_register_deposits.0 - register_deposit).into();
+        ledger.reserved_register_deposit_refunds =
+            (ledger.reserved_register_deposit_refunds.0 + refundable).into();
+        ledger.withdrawable_fees =
+            (ledger.withdrawable_fees.0 + register_deposit - refundable).into();
+    }
+    /// Move the unclaimed register deposit refund of an appchain to the claimable
+    /// register deposits of its owner. Should be called before the appchain is removed.
+    pub fn internal_release_register_deposit_of(&mut self, appchain_id: &AppchainId) {
+        let mut appchain_basedata = match self.appchain_basedatas.get(appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => return,
+        };
+        // The register deposit of an appchain which is forced to close may not be settled yet.
+        self.internal_settle_register_deposit(&mut appchain_basedata);
+        if appchain_basedata.refunded_register_deposit.is_some() {
+            return;
+        }
+        let refundable = appchain_basedata.refundable_register_deposit.unwrap_or(0);
+        if refundable > 0 {
+            appchain_basedata.set_refunded_register_deposit(Some(refundable));
+            self.appchain_basedatas
+                .insert(appchain_id, &appchain_basedata);
+            self.internal_increase_claimable_register_deposit(
+                &appchain_basedata.owner(),
+                &appchain_basedata.register_deposit_asset(),
+                refundable,
+            );
+            log!(
+                "Unclaimed register deposit refund of appchain '{}' can be claimed by '{}'. Amount: '{}'",
+                appchain_id,
+                appchain_basedata.owner(),
+                refundable
+            );
+        }
+    }
+    /// Add the given amount to the claimable register deposit refunds of an account.
+    /// The amount should be kept in the reserved register deposit refunds of the ledger.
+    pub fn internal_increase_claimable_register_deposit(
+        &mut self,
+        account_id: &AccountId,
+        asset: &RegisterDepositAsset,
+        amount: Balance,
+    ) {
+        let key = (account_id.clone(), asset.clone());
+        let claimable = self.claimable_register_deposits.get(&key).unwrap_or(0);
+        self.claimable_register_deposits
+            .insert(&key, &(claimable + amount));
+    }
+    /// Transfer the given amount of a register deposit asset to the receiver.
+    pub fn internal_transfer_asset(
+        &self,
+        asset: &RegisterDepositAsset,
+        receiver_id: &AccountId,
+        amount: Balance,
+    ) -> Promise {
+        match asset {
+            RegisterDepositAsset::Near => Promise::new(receiver_id.clone()).transfer(amount),
+            RegisterDepositAsset::FungibleToken(contract_id) => {
+                ext_ft_core::ext(contract_id.clone())
+                    .with_attached_deposit(1)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                    .with_unused_gas_weight(0)
+                    .ft_transfer(receiver_id.clone(), amount.into(), None)
+            }
+        }
+    }
+}
@@ -8,6 +8,9 @@ pub trait VoterActions {
     fn withdraw_upvote_deposit_of(&mut self, appchain_id: AppchainId, amount: U128);
     /// Withdraw a certain amount of downvote deposit for an appchain
     fn withdraw_downvote_deposit_of(&mut self, appchain_id: AppchainId, amount: U128);
+    /// Claim the vote deposits of the removed appchains which failed to be withdrawn
+    /// or refunded to the caller.
+    fn claim_vote_deposits_of_removed_appchains(&mut self);
 }
 
 /// The actions which the voter can perform
@@ -28,11 +31,13 @@ impl VoterActions for AppchainRegistry {
         );
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
         appchain_basedata.decrease_upvote_deposit(amount.0);
+        appchain_basedata.increase_pending_vote_deposit_refunds();
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         if amount.0 == voter_upvote {
             self.upvote_deposits
                 .remove(&(appchain_id.clone(), voter.clone()));
+            self.internal_remove_voter_without_deposit(&appchain_id, &voter);
         } else {
             self.upvote_deposits.insert(
                 &(appchain_id.clone(), voter.clone()),
@@ -66,11 +71,13 @@ impl VoterActions for AppchainRegistry {
         );
         let mut appchain_basedata = self.get_appchain_basedata(&appchain_id);
         appchain_basedata.decrease_downvote_deposit(amount.0);
+        appchain_basedata.increase_pending_vote_deposit_refunds();
         self.appchain_basedatas
             .insert(&appchain_id, &appchain_basedata);
         if amount.0 == voter_downvote {
             self.downvote_deposits
                 .remove(&(appchain_id.clone(), voter.clone()));
+            self.internal_remove_voter_without_deposit(&appchain_id, &voter);
         } else {
             self.downvote_deposits.insert(
                 &(appchain_id.clone(), voter.clone()),
@@ -86,7 +93,28 @@ impl VoterActions for AppchainRegistry {
                 ext_self::ext(env::current_account_id())
                     .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
                     .with_unused_gas_weight(0)
-                    .resolve_withdraw_upvote_deposit(appchain_id.clone(), voter.clone(), amount),
+                    .resolve_withdraw_downvote_deposit(appchain_id.clone(), voter.clone(), amount),
+            );
+    }
+    //
+    fn claim_vote_deposits_of_removed_appchains(&mut self) {
+        self.assert_asset_transfer_is_not_paused();
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .claimable_vote_deposits
+            .remove(&account_id)
+            .unwrap_or(0);
+        assert!(amount > 0, "Nothing to claim.");
+        ext_ft_core::ext(self.oct_token.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(account_id.clone(), amount.into(), None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_claimable_vote_deposit_refund(account_id, amount.into()),
             );
     }
 }
@@ -101,6 +129,20 @@ impl SelfCallback for AppchainRegistry {
         amount: U128,
     ) {
         assert_self();
+        // The appchain can NOT be removed while the withdrawal is in flight,
+        // this is only a safeguard.
+        let mut appchain_basedata = match self.appchain_basedatas.get(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => {
+                self.internal_resolve_vote_deposits_of_removed_appchain(
+                    &appchain_id,
+                    &account_id,
+                    amount.0,
+                );
+                return;
+            }
+        };
+        appchain_basedata.decrease_pending_vote_deposit_refunds();
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Successful(_) => log!(
@@ -109,8 +151,27 @@ impl SelfCallback for AppchainRegistry {
                 &account_id,
                 &amount.0
             ),
-            PromiseResult::Failed => {}
+            PromiseResult::Failed => {
+                let voter_upvote = self
+                    .upvote_deposits
+                    .get(&(appchain_id.clone(), account_id.clone()))
+                    .unwrap_or_default();
+                self.upvote_deposits.insert(
+                    &(appchain_id.clone(), account_id.clone()),
+                    &(voter_upvote + amount.0),
+                );
+                self.internal_add_voter(&appchain_id, &account_id);
+                appchain_basedata.increase_upvote_deposit(amount.0);
+                log!(
+                    "Failed to withdraw upvote for appchain '{}' by '{}'. Amount: '{}'. The upvote deposit is restored.",
+                    &appchain_id,
+                    &account_id,
+                    &amount.0
+                );
+            }
         }
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
     }
     //
     fn resolve_withdraw_downvote_deposit(
@@ -120,6 +181,20 @@ impl SelfCallback for AppchainRegistry {
         amount: U128,
     ) {
         assert_self();
+        // The appchain can NOT be removed while the withdrawal is in flight,
+        // this is only a safeguard.
+        let mut appchain_basedata = match self.appchain_basedatas.get(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => {
+                self.internal_resolve_vote_deposits_of_removed_appchain(
+                    &appchain_id,
+                    &account_id,
+                    amount.0,
+                );
+                return;
+            }
+        };
+        appchain_basedata.decrease_pending_vote_deposit_refunds();
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Successful(_) => log!(
@@ -128,7 +203,575 @@ impl SelfCallback for AppchainRegistry {
                 &account_id,
                 &amount.0
             ),
-            PromiseResult::Failed => {}
+            PromiseResult::Failed => {
+                let voter_downvote = self
+                    .downvote_deposits
+                    .get(&(appchain_id.clone(), account_id.clone()))
+                    .unwrap_or_default();
+                self.downvote_deposits.insert(
+                    &(appchain_id.clone(), account_id.clone()),
+                    &(voter_downvote + amount.0),
+                );
+                self.internal_add_voter(&appchain_id, &account_id);
+                appchain_basedata.increase_downvote_deposit(amount.0);
+                log!(
+                    "Failed to withdraw downvote for appchain '{}' by '{}'. Amount: '{}'. The downvote deposit is restored.",
+                    &appchain_id,
+                    &account_id,
+                    &amount.0
+                );
+            }
+        }
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+    }
+    //
+    fn resolve_vote_deposits_refund(
+        &mut self,
+        appchain_id: AppchainId,
+        account_id: AccountId,
+        upvote_deposit: U128,
+        downvote_deposit: U128,
+    ) {
+        assert_self();
+        // The appchain can NOT be removed while the refund is in flight,
+        // this is only a safeguard.
+        let mut appchain_basedata = match self.appchain_basedatas.get(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => {
+                self.internal_resolve_vote_deposits_of_removed_appchain(
+                    &appchain_id,
+                    &account_id,
+                    upvote_deposit.0 + downvote_deposit.0,
+                );
+                return;
+            }
+        };
+        appchain_basedata.decrease_pending_vote_deposit_refunds();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Vote deposits for appchain '{}' refunded to '{}'. Upvote: '{}', downvote: '{}'",
+                &appchain_id,
+                &account_id,
+                &upvote_deposit.0,
+                &downvote_deposit.0
+            ),
+            PromiseResult::Failed => {
+                let key = (appchain_id.clone(), account_id.clone());
+                if upvote_deposit.0 > 0 {
+                    let voter_upvote = self.upvote_deposits.get(&key).unwrap_or_default();
+                    self.upvote_deposits
+                        .insert(&key, &(voter_upvote + upvote_deposit.0));
+                }
+                if downvote_deposit.0 > 0 {
+                    let voter_downvote = self.downvote_deposits.get(&key).unwrap_or_default();
+                    self.downvote_deposits
+                        .insert(&key, &(voter_downvote + downvote_deposit.0));
+                }
+                self.internal_add_voter(&appchain_id, &account_id);
+                appchain_basedata.increase_upvote_deposit(upvote_deposit.0);
+                appchain_basedata.increase_downvote_deposit(downvote_deposit.0);
+                log!(
+                    "Failed to refund vote deposits for appchain '{}' to '{}'. Upvote: '{}', downvote: '{}'. The vote deposits are restored.",
+                    &appchain_id,
+                    &account_id,
+                    &upvote_deposit.0,
+                    &downvote_deposit.0
+                );
+            }
+        }
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+    }
+    //
+    fn resolve_register_deposit_refund(
+        &mut self,
+        appchain_id: AppchainId,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Register deposit of appchain '{}' refunded to '{}'. Amount: '{}'",
+                &appchain_id,
+                &account_id,
+                &amount.0
+            ),
+            PromiseResult::Failed => {
+                let ledger = self.registry_treasury.ledger_of_mut(&asset);
+                ledger.reserved_register_deposit_refunds =
+                    (ledger.reserved_register_deposit_refunds.0 + amount.0).into();
+                if let Some(mut appchain_basedata) = self.appchain_basedatas.get(&appchain_id) {
+                    appchain_basedata.set_refunded_register_deposit(None);
+                    self.appchain_basedatas
+                        .insert(&appchain_id, &appchain_basedata);
+                } else {
+                    self.internal_increase_claimable_register_deposit(
+                        &account_id,
+                        &asset,
+                        amount.0,
+                    );
+                }
+                log!(
+                    "Failed to refund register deposit of appchain '{}' to '{}'. Amount: '{}'. The refund can be claimed again.",
+                    &appchain_id,
+                    &account_id,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_claimable_register_deposit_refund(
+        &mut self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Register deposit refunds of removed appchains refunded to '{}'. Asset: '{:?}', amount: '{}'",
+                &account_id,
+                &asset,
+                &amount.0
+            ),
+            PromiseResult::Failed => {
+                let ledger = self.registry_treasury.ledger_of_mut(&asset);
+                ledger.reserved_register_deposit_refunds =
+                    (ledger.reserved_register_deposit_refunds.0 + amount.0).into();
+                self.internal_increase_claimable_register_deposit(&account_id, &asset, amount.0);
+                log!(
+                    "Failed to refund register deposit refunds of removed appchains to '{}'. Asset: '{:?}', amount: '{}'. The refunds can be claimed again.",
+                    &account_id,
+                    &asset,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_claimable_vote_deposit_refund(&mut self, account_id: AccountId, amount: U128) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Vote deposits of removed appchains refunded to '{}'. Amount: '{}'",
+                &account_id,
+                &amount.0
+            ),
+            PromiseResult::Failed => {
+                self.internal_increase_claimable_vote_deposit(&account_id, amount.0);
+                log!(
+                    "Failed to refund vote deposits of removed appchains to '{}'. Amount: '{}'. The deposits can be claimed again.",
+                    &account_id,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_treasury_sweep(
+        &mut self,
+        account_id: AccountId,
+        asset: RegisterDepositAsset,
+        amount: U128,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Fees of appchain registry swept to '{}'. Asset: '{:?}', amount: '{}'",
+                &account_id,
+                &asset,
+                &amount.0
+            ),
+            PromiseResult::Failed => {
+                let ledger = self.registry_treasury.ledger_of_mut(&asset);
+                ledger.withdrawable_fees = (ledger.withdrawable_fees.0 + amount.0).into();
+                ledger.swept_fees = (ledger.swept_fees.0 - amount.0).into();
+                log!(
+                    "Failed to sweep fees of appchain registry to '{}'. Asset: '{:?}', amount: '{}'. The fees are restored.",
+                    &account_id,
+                    &asset,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_anchor_deployment(&mut self, appchain_id: AppchainId) {
+        assert_self();
+        let status = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                log!("Anchor contract of appchain '{}' deployed.", &appchain_id);
+                AnchorDeploymentStatus::Deployed
+            }
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to deploy anchor contract of appchain '{}'. The anchor account is NOT created.",
+                    &appchain_id
+                );
+                AnchorDeploymentStatus::Failed
+            }
+        };
+        if let Some(mut appchain_basedata) = self.appchain_basedatas.get(&appchain_id) {
+            appchain_basedata.set_anchor_deployment_status(status);
+            self.appchain_basedatas
+                .insert(&appchain_id, &appchain_basedata);
+        }
+    }
+    //
+    fn resolve_anchor_upgrade(
+        &mut self,
+        appchain_id: AppchainId,
+        caller: AccountId,
+        storage_cost: U128,
+    ) {
+        assert_self();
+        let succeeded = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                log!("Anchor contract of appchain '{}' upgraded.", &appchain_id);
+                true
+            }
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to upgrade anchor contract of appchain '{}'.",
+                    &appchain_id
+                );
+                if storage_cost.0 > 0 {
+                    Promise::new(caller).transfer(storage_cost.0);
+                }
+                false
+            }
+        };
+        if let Some(mut appchain_basedata) = self.appchain_basedatas.get(&appchain_id) {
+            appchain_basedata.finish_anchor_upgrade(succeeded);
+            self.appchain_basedatas
+                .insert(&appchain_id, &appchain_basedata);
+        }
+    }
+    //
+    fn resolve_anchor_teardown(&mut self, appchain_id: AppchainId) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                log!(
+                    "Anchor account of appchain '{}' is deleted, its balance is returned to registry.",
+                    &appchain_id
+                );
+                if let Some(mut appchain_basedata) = self.appchain_basedatas.get(&appchain_id) {
+                    match self.check_appchain_removable(&appchain_basedata) {
+                        Ok(()) => {
+                            self.internal_remove_appchain(&appchain_id);
+                            log!("Appchain '{}' is removed from registry.", &appchain_id);
+                        }
+                        Err(reason) => {
+                            appchain_basedata
+                                .set_anchor_teardown_status(AnchorTeardownStatus::TornDown);
+                            self.appchain_basedatas
+                                .insert(&appchain_id, &appchain_basedata);
+                            log!("{} The appchain is NOT removed.", reason);
+                        }
+                    }
+                }
+            }
+            PromiseResult::Failed => {
+                if let Some(mut appchain_basedata) = self.appchain_basedatas.get(&appchain_id) {
+                    appchain_basedata.set_anchor_teardown_status(AnchorTeardownStatus::Failed);
+                    self.appchain_basedatas
+                        .insert(&appchain_id, &appchain_basedata);
+                }
+                log!(
+                    "Failed to tear down anchor account of appchain '{}'. The appchain is NOT removed.",
+                    &appchain_id
+                );
+            }
+        }
+    }
+    //
+    fn resolve_anchor_function_call(&mut self, call_id: U64) {
+        assert_self();
+        let mut record = self.anchor_function_calls.get(&call_id.0).unwrap();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                record.status = AnchorFunctionCallStatus::Succeeded;
+                if value.len() > MAX_BYTES_OF_ANCHOR_FUNCTION_RETURN_VALUE {
+                    log!(
+                        "The return value ({} bytes) is truncated to {} bytes.",
+                        value.len(),
+                        MAX_BYTES_OF_ANCHOR_FUNCTION_RETURN_VALUE
+                    );
+                }
+                record.return_value = Some(
+                    value
+                        .into_iter()
+                        .take(MAX_BYTES_OF_ANCHOR_FUNCTION_RETURN_VALUE)
+                        .collect::<Vec<u8>>()
+                        .into(),
+                );
+                log!(
+                    "Function '{}' of anchor of appchain '{}' is called. Call id: '{}'",
+                    &record.function_name,
+                    &record.appchain_id,
+                    &call_id.0
+                );
+            }
+            PromiseResult::Failed => {
+                record.status = AnchorFunctionCallStatus::Failed;
+                if record.deposit.0 > 0 {
+                    Promise::new(record.caller.clone()).transfer(record.deposit.0);
+                }
+                log!(
+                    "Failed to call function '{}' of anchor of appchain '{}'. Call id: '{}'",
+                    &record.function_name,
+                    &record.appchain_id,
+                    &call_id.0
+                );
+            }
+        }
+        self.anchor_function_calls.insert(&call_id.0, &record);
+    }
+}
+
+impl AppchainRegistry {
+    /// Resolve a withdrawal (or refund) of vote deposits of an appchain which is removed.
+    ///
+    /// The voter and the deposits are NOT restored for the removed appchain,
+    /// the amount failed to be transferred is kept as claimable for the voter instead.
+    fn internal_resolve_vote_deposits_of_removed_appchain(
+        &mut self,
+        appchain_id: &AppchainId,
+        account_id: &AccountId,
+        amount: Balance,
+    ) {
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => log!(
+                "Vote deposits of removed appchain '{}' transferred to '{}'. Amount: '{}'",
+                appchain_id,
+                account_id,
+                &amount
+            ),
+            PromiseResult::Failed => {
+                self.internal_increase_claimable_vote_deposit(account_id, amount);
+                log!(
+                    "Failed to transfer vote deposits of removed appchain '{}' to '{}'. Amount: '{}'. The deposits can be claimed by the voter.",
+                    appchain_id,
+                    account_id,
+                    &amount
+                );
+            }
+        }
+    }
+    //
+    fn internal_increase_claimable_vote_deposit(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+    ) {
+        let claimable = self.claimable_vote_deposits.get(account_id).unwrap_or(0);
+        self.claimable_vote_deposits
+            .insert(account_id, &(claimable + amount));
+    }
+    /// Record the upvote deposit of a voter for an appchain in 'voting' state.
+    ///
+    /// Return the amount of the deposit which is not used.
+    pub fn internal_upvote_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        voter: AccountId,
+        amount: Balance,
+    ) -> PromiseOrValue<U128> {
+        let mut appchain_basedata = match self.get_voting_appchain_basedata(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => return PromiseOrValue::Value(amount.into()),
+        };
+        appchain_basedata.increase_upvote_deposit(amount);
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        let voter_upvote = self
+            .upvote_deposits
+            .get(&(appchain_id.clone(), voter.clone()))
+            .unwrap_or_default();
+        self.upvote_deposits.insert(
+            &(appchain_id.clone(), voter.clone()),
+            &(voter_upvote + amount),
+        );
+        self.internal_add_voter(&appchain_id, &voter);
+        log!(
+            "Received upvote for appchain '{}' from '{}'. Amount: '{}'",
+            &appchain_id,
+            &voter,
+            &amount
+        );
+        PromiseOrValue::Value(0.into())
+    }
+    /// Record the downvote deposit of a voter for an appchain in 'voting' state.
+    ///
+    /// Return the amount of the deposit which is not used.
+    pub fn internal_downvote_appchain(
+        &mut self,
+        appchain_id: AppchainId,
+        voter: AccountId,
+        amount: Balance,
+    ) -> PromiseOrValue<U128> {
+        let mut appchain_basedata = match self.get_voting_appchain_basedata(&appchain_id) {
+            Some(appchain_basedata) => appchain_basedata,
+            None => return PromiseOrValue::Value(amount.into()),
+        };
+        appchain_basedata.increase_downvote_deposit(amount);
+        self.appchain_basedatas
+            .insert(&appchain_id, &appchain_basedata);
+        let voter_downvote = self
+            .downvote_deposits
+            .get(&(appchain_id.clone(), voter.clone()))
+            .unwrap_or_default();
+        self.downvote_deposits.insert(
+            &(appchain_id.clone(), voter.clone()),
+            &(voter_downvote + amount),
+        );
+        self.internal_add_voter(&appchain_id, &voter);
+        log!(
+            "Received downvote for appchain '{}' from '{}'. Amount: '{}'",
+            &appchain_id,
+            &voter,
+            &amount
+        );
+        PromiseOrValue::Value(0.into())
+    }
+    // Get the basedata of an appchain which can accept votes, or log the reason of refusal.
+    fn get_voting_appchain_basedata(&self, appchain_id: &AppchainId) -> Option<AppchainBasedata> {
+        match self.appchain_basedatas.get(appchain_id) {
+            Some(appchain_basedata) => {
+                if !appchain_basedata.state().eq(&AppchainState::Voting) {
+                    log!(
+                        "Appchain '{}' is '{}', which can NOT be voted. Return deposit.",
+                        appchain_id,
+                        appchain_basedata.state()
+                    );
+                    None
+                } else if appchain_basedata
+                    .voting_deadline
+                    .is_some_and(|deadline| env::block_timestamp() >= deadline)
+                {
+                    log!(
+                        "The voting of appchain '{}' has expired. Return deposit.",
+                        appchain_id
+                    );
+                    None
+                } else {
+                    Some(appchain_basedata)
+                }
+            }
+            None => {
+                log!(
+                    "Appchain '{}' is not found, which can NOT be voted. Return deposit.",
+                    appchain_id
+                );
+                None
+            }
+        }
+    }
+    /// Add a voter to the voter index of an appchain, and the appchain to
+    /// the voted appchains of the voter.
+    pub fn internal_add_voter(&mut self, appchain_id: &AppchainId, voter: &AccountId) {
+        let mut voters = self.appchain_voters.get(appchain_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::AppchainVotersOf(appchain_id.clone()).into_bytes())
+        });
+        if voters.insert(voter) {
+            self.appchain_voters.insert(appchain_id, &voters);
+        }
+        let mut voted_appchains = self.voted_appchains.get(voter).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::VotedAppchainsOf(voter.clone()).into_bytes())
+        });
+        if voted_appchains.insert(appchain_id) {
+            self.voted_appchains.insert(voter, &voted_appchains);
+        }
+    }
+    /// Remove a voter from the voter index of an appchain, if the voter has
+    /// neither upvote deposit nor downvote deposit for the appchain.
+    pub fn internal_remove_voter_without_deposit(
+        &mut self,
+        appchain_id: &AppchainId,
+        voter: &AccountId,
+    ) {
+        let key = (appchain_id.clone(), voter.clone());
+        if self.upvote_deposits.get(&key).is_some() || self.downvote_deposits.get(&key).is_some() {
+            return;
+        }
+        if let Some(mut voters) = self.appchain_voters.get(appchain_id) {
+            voters.remove(voter);
+            if voters.is_empty() {
+                self.appchain_voters.remove(appchain_id);
+            } else {
+                self.appchain_voters.insert(appchain_id, &voters);
+            }
+        }
+        self.internal_remove_voted_appchain_of(voter, appchain_id);
+    }
+    /// Remove an appchain from the voted appchains of a voter.
+    pub fn internal_remove_voted_appchain_of(
+        &mut self,
+        voter: &AccountId,
+        appchain_id: &AppchainId,
+    ) {
+        if let Some(mut voted_appchains) = self.voted_appchains.get(voter) {
+            voted_appchains.remove(appchain_id);
+            if voted_appchains.is_empty() {
+                self.voted_appchains.remove(voter);
+            } else {
+                self.voted_appchains.insert(voter, &voted_appchains);
+            }
+        }
+    }
+    /// Refund all vote deposits of a voter for an appchain.
+    pub fn internal_refund_vote_deposits_of(
+        &mut self,
+        appchain_id: &AppchainId,
+        voter: &AccountId,
+    ) {
+        let key = (appchain_id.clone(), voter.clone());
+        let upvote_deposit = self.upvote_deposits.remove(&key).unwrap_or_default();
+        let downvote_deposit = self.downvote_deposits.remove(&key).unwrap_or_default();
+        self.internal_remove_voter_without_deposit(appchain_id, voter);
+        if upvote_deposit + downvote_deposit == 0 {
+            return;
         }
+        let mut appchain_basedata = self.get_appchain_basedata(appchain_id);
+        appchain_basedata.decrease_upvote_deposit(upvote_deposit);
+        appchain_basedata.decrease_downvote_deposit(downvote_deposit);
+        appchain_basedata.increase_pending_vote_deposit_refunds();
+        self.appchain_basedatas
+            .insert(appchain_id, &appchain_basedata);
+        ext_ft_core::ext(self.oct_token.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(
+                voter.clone(),
+                (upvote_deposit + downvote_deposit).into(),
+                None,
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_vote_deposits_refund(
+                        appchain_id.clone(),
+                        voter.clone(),
+                        upvote_deposit.into(),
+                        downvote_deposit.into(),
+                    ),
+            );
     }
 }
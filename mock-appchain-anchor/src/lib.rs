@@ -1,11 +1,14 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 
-use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault};
+use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault, Promise};
+
+const ANCHOR_WASM_KEY: &[u8] = b"aw";
 
 #[near_bindgen]
 #[derive(BorshSerialize, BorshDeserialize, PanicOnDefault)]
 pub struct MockAppchainAnchor {
     appchain_id: String,
+    appchain_registry: AccountId,
     owner: AccountId,
     oct_token: AccountId,
 }
@@ -13,12 +16,40 @@ pub struct MockAppchainAnchor {
 #[near_bindgen]
 impl MockAppchainAnchor {
     #[init]
-    pub fn new(appchain_id: String, oct_token: AccountId) -> Self {
+    pub fn new(appchain_id: String, appchain_registry: AccountId, oct_token: AccountId) -> Self {
         assert!(!env::state_exists(), "Already initialized");
         Self {
             appchain_id,
+            appchain_registry,
             owner: env::signer_account_id(),
             oct_token,
         }
     }
+    /// Delete this account with the given beneficiary, can only be called by registry.
+    pub fn teardown(&mut self, beneficiary: AccountId) -> Promise {
+        assert_eq!(env::predecessor_account_id(), self.appchain_registry);
+        Promise::new(env::current_account_id()).delete_account(beneficiary)
+    }
+}
+
+/// Stores attached data as the new code of this contract, can only be called by registry.
+#[no_mangle]
+pub extern "C" fn store_wasm_of_self() {
+    env::setup_panic_hook();
+    let contract: MockAppchainAnchor = env::state_read().expect("ERR_CONTRACT_IS_NOT_INITIALIZED");
+    assert_eq!(env::predecessor_account_id(), contract.appchain_registry);
+    let input = env::input().expect("ERR_NO_INPUT");
+    env::storage_write(ANCHOR_WASM_KEY, &input);
+}
+
+/// Deploys the stored code to this contract, can only be called by registry.
+#[no_mangle]
+pub extern "C" fn update_self() {
+    env::setup_panic_hook();
+    let contract: MockAppchainAnchor = env::state_read().expect("ERR_CONTRACT_IS_NOT_INITIALIZED");
+    assert_eq!(env::predecessor_account_id(), contract.appchain_registry);
+    let code = env::storage_read(ANCHOR_WASM_KEY).expect("ERR_NO_STAGED_CODE");
+    let promise_id = env::promise_batch_create(&env::current_account_id());
+    env::promise_batch_action_deploy_contract(promise_id, &code);
+    env::promise_return(promise_id);
 }
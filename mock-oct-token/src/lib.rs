@@ -1,6 +1,8 @@
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider,
 };
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
 use near_contract_standards::fungible_token::FungibleToken;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::LazyOption;
@@ -12,6 +14,8 @@ use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault, PromiseOrValue};
 pub struct MockOctToken {
     token: FungibleToken,
     metadata: LazyOption<FungibleTokenMetadata>,
+    /// Whether the `ft_transfer` should be rejected, for testing failed transfers
+    ft_transfer_is_rejected: bool,
 }
 
 #[near_bindgen]
@@ -23,14 +27,65 @@ impl MockOctToken {
         let mut this = Self {
             token: FungibleToken::new(b"a".to_vec()),
             metadata: LazyOption::new(b"m".to_vec(), Some(&metadata)),
+            ft_transfer_is_rejected: false,
         };
         this.token.internal_register_account(&owner_id);
         this.token.internal_deposit(&owner_id, total_supply.into());
         this
     }
+    //
+    pub fn set_ft_transfer_is_rejected(&mut self, value: bool) {
+        self.ft_transfer_is_rejected = value;
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenCore for MockOctToken {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        assert!(
+            !self.ft_transfer_is_rejected,
+            "The ft_transfer is rejected by mock token."
+        );
+        self.token.ft_transfer(receiver_id, amount, memo)
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.token.ft_transfer_call(receiver_id, amount, memo, msg)
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenResolver for MockOctToken {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        let (used_amount, _) =
+            self.token
+                .internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+        used_amount.into()
+    }
 }
 
-near_contract_standards::impl_fungible_token_core!(MockOctToken, token);
 near_contract_standards::impl_fungible_token_storage!(MockOctToken, token);
 
 #[near_bindgen]
@@ -1,3 +1,4 @@
+use crate::contract_interfaces;
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::{
     json_types::{Base64VecU8, U128},
@@ -91,6 +92,7 @@ pub async fn initialize_contracts_and_users(
         .await?
         .unwrap();
     register_user_to_ft_contract(&alice, &oct_token).await?;
+    register_user_to_registry(&alice, &appchain_registry).await?;
     super::call_ft_transfer(&root, &alice, total_supply / 10, &oct_token)
         .await?
         .unwrap();
@@ -103,6 +105,7 @@ pub async fn initialize_contracts_and_users(
         .await?
         .unwrap();
     register_user_to_ft_contract(&bob, &oct_token).await?;
+    register_user_to_registry(&bob, &appchain_registry).await?;
     super::call_ft_transfer(&root, &bob, total_supply / 10, &oct_token)
         .await?
         .unwrap();
@@ -115,6 +118,7 @@ pub async fn initialize_contracts_and_users(
         .await?
         .unwrap();
     register_user_to_ft_contract(&charlie, &oct_token).await?;
+    register_user_to_registry(&charlie, &appchain_registry).await?;
     super::call_ft_transfer(&root, &charlie, total_supply / 10, &oct_token)
         .await?
         .unwrap();
@@ -127,6 +131,7 @@ pub async fn initialize_contracts_and_users(
         .await?
         .unwrap();
     register_user_to_ft_contract(&dave, &oct_token).await?;
+    register_user_to_registry(&dave, &appchain_registry).await?;
     super::call_ft_transfer(&root, &dave, total_supply / 10, &oct_token)
         .await?
         .unwrap();
@@ -139,6 +144,7 @@ pub async fn initialize_contracts_and_users(
         .await?
         .unwrap();
     register_user_to_ft_contract(&eve, &oct_token).await?;
+    register_user_to_registry(&eve, &appchain_registry).await?;
     super::call_ft_transfer(&root, &eve, total_supply / 10, &oct_token)
         .await?
         .unwrap();
@@ -154,6 +160,22 @@ pub async fn initialize_contracts_and_users(
     Ok((root, oct_token, appchain_registry, council, users))
 }
 
+// Deposit storage balance for the given `user` in appchain registry contract
+pub async fn register_user_to_registry(
+    account: &Account,
+    appchain_registry: &Contract,
+) -> anyhow::Result<()> {
+    assert!(contract_interfaces::storage_management::storage_deposit(
+        account,
+        appchain_registry,
+        parse_near!("1 N"),
+    )
+    .await
+    .expect("Failed to call 'storage_deposit' of appchain registry.")
+    .is_success());
+    Ok(())
+}
+
 // Register the given `user` to fungible token contract
 pub async fn register_user_to_ft_contract(
     account: &Account,
@@ -0,0 +1,71 @@
+use appchain_registry::types::AppchainType;
+use near_sdk::serde_json::json;
+use near_units::parse_near;
+use workspaces::{result::ExecutionFinalResult, Account, Contract};
+
+pub async fn store_wasm_of_appchain_anchor(
+    signer: &Account,
+    registry: &Contract,
+    wasm: Vec<u8>,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "store_wasm_of_appchain_anchor")
+        .args(wasm)
+        .gas(200_000_000_000_000)
+        .deposit(parse_near!("3 N"))
+        .transact()
+        .await
+}
+
+pub async fn register_anchor_template(
+    signer: &Account,
+    registry: &Contract,
+    appchain_type: AppchainType,
+    version: &str,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "register_anchor_template")
+        .args_json(json!({
+            "appchain_type": appchain_type,
+            "version": version,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn deprecate_anchor_template(
+    signer: &Account,
+    registry: &Contract,
+    appchain_type: AppchainType,
+    version: &str,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "deprecate_anchor_template")
+        .args_json(json!({
+            "appchain_type": appchain_type,
+            "version": version,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn upgrade_appchain_anchors(
+    signer: &Account,
+    registry: &Contract,
+    appchain_ids: Vec<String>,
+    version: Option<&str>,
+    deposit: u128,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "upgrade_appchain_anchors")
+        .args_json(json!({
+            "appchain_ids": appchain_ids,
+            "version": version,
+        }))
+        .deposit(deposit)
+        .gas(300_000_000_000_000)
+        .transact()
+        .await
+}
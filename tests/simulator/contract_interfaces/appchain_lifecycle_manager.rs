@@ -1,3 +1,4 @@
+use appchain_registry::types::{AppchainRejectionCode, AuditReport};
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_sdk::{json_types::U128, serde_json::json, AccountId};
 use std::collections::HashMap;
@@ -62,10 +63,14 @@ pub async fn pass_auditing_appchain(
     signer: &Account,
     registry: &Contract,
     appchain_id: &String,
+    audit_report: Option<AuditReport>,
 ) -> Result<ExecutionFinalResult, workspaces::error::Error> {
     signer
         .call(registry.id(), "pass_auditing_appchain")
-        .args_json(json!({ "appchain_id": appchain_id }))
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "audit_report": audit_report,
+        }))
         .gas(200_000_000_000_000)
         .transact()
         .await
@@ -75,10 +80,16 @@ pub async fn reject_appchain(
     signer: &Account,
     registry: &Contract,
     appchain_id: &String,
+    reason_code: AppchainRejectionCode,
+    reason: &str,
 ) -> Result<ExecutionFinalResult, workspaces::error::Error> {
     signer
         .call(registry.id(), "reject_appchain")
-        .args_json(json!({ "appchain_id": appchain_id }))
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "reason_code": reason_code,
+            "reason": reason,
+        }))
         .gas(200_000_000_000_000)
         .transact()
         .await
@@ -104,10 +115,14 @@ pub async fn remove_appchain(
     signer: &Account,
     registry: &Contract,
     appchain_id: &String,
+    teardown_anchor: bool,
 ) -> Result<ExecutionFinalResult, workspaces::error::Error> {
     signer
         .call(registry.id(), "remove_appchain")
-        .args_json(json!({ "appchain_id": appchain_id }))
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "teardown_anchor": teardown_anchor,
+        }))
         .gas(200_000_000_000_000)
         .transact()
         .await
@@ -1,5 +1,5 @@
 use crate::common;
-use appchain_registry::types::SubstrateTemplateType;
+use appchain_registry::types::{AppchainType, RegisterDepositAsset, SubstrateTemplateType};
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 use near_sdk::{
     json_types::U128,
@@ -62,6 +62,72 @@ pub async fn register_appchain(
     ).await
 }
 
+pub async fn register_appchain_with_near(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+    appchain_type: AppchainType,
+    premined_wrapped_appchain_token_beneficiary: AccountId,
+    fungible_token_metadata: FungibleTokenMetadata,
+    deposit: u128,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "register_appchain")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "description": "appchain description",
+            "appchain_type": appchain_type,
+            "evm_chain_id": null,
+            "website_url": "http://ddfs.dsdfs",
+            "github_address": "https://jldfs.yoasdfasd",
+            "contact_email": "joe@lksdf.com",
+            "premined_wrapped_appchain_token_beneficiary": premined_wrapped_appchain_token_beneficiary,
+            "premined_wrapped_appchain_token": U128::from(10000000),
+            "initial_supply_of_wrapped_appchain_token": U128::from(10000000),
+            "ido_amount_of_wrapped_appchain_token": U128::from(1000000),
+            "initial_era_reward": U128::from(100),
+            "fungible_token_metadata": fungible_token_metadata,
+            "custom_metadata": HashMap::<String, String>::new()
+        }))
+        .deposit(deposit)
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn reapply_appchain_with_near(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+    appchain_type: AppchainType,
+    premined_wrapped_appchain_token_beneficiary: AccountId,
+    fungible_token_metadata: FungibleTokenMetadata,
+    deposit: u128,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "reapply_appchain")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "description": "appchain description (reapplied)",
+            "appchain_type": appchain_type,
+            "evm_chain_id": null,
+            "website_url": "http://ddfs.dsdfs",
+            "github_address": "https://jldfs.yoasdfasd",
+            "contact_email": "joe@lksdf.com",
+            "premined_wrapped_appchain_token_beneficiary": premined_wrapped_appchain_token_beneficiary,
+            "premined_wrapped_appchain_token": U128::from(10000000),
+            "initial_supply_of_wrapped_appchain_token": U128::from(10000000),
+            "ido_amount_of_wrapped_appchain_token": U128::from(1000000),
+            "initial_era_reward": U128::from(100),
+            "fungible_token_metadata": fungible_token_metadata,
+            "custom_metadata": HashMap::<String, String>::new()
+        }))
+        .deposit(deposit)
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
 pub async fn transfer_appchain_ownership(
     signer: &Account,
     registry: &Contract,
@@ -78,3 +144,45 @@ pub async fn transfer_appchain_ownership(
         .transact()
         .await
 }
+
+pub async fn withdraw_appchain(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "withdraw_appchain")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn claim_register_deposit_refund(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "claim_register_deposit_refund")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn claim_register_deposit_refunds_of_removed_appchains(
+    signer: &Account,
+    registry: &Contract,
+    asset: &RegisterDepositAsset,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(
+            registry.id(),
+            "claim_register_deposit_refunds_of_removed_appchains",
+        )
+        .args_json(json!({ "asset": asset }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -0,0 +1,24 @@
+use appchain_registry::types::AuditReport;
+use near_sdk::serde_json::json;
+use workspaces::{result::ExecutionFinalResult, Account, Contract};
+
+pub async fn submit_audit_attestation(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+    approved: bool,
+    audit_report: Option<AuditReport>,
+    comment: &str,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "submit_audit_attestation")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "approved": approved,
+            "audit_report": audit_report,
+            "comment": comment,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -1,6 +1,12 @@
+pub mod anchor_template_manager;
 pub mod appchain_lifecycle_manager;
 pub mod appchain_owner_actions;
+pub mod auditor_actions;
+pub mod permissionless_actions;
 pub mod registry_roles;
 pub mod registry_settings;
 pub mod registry_viewer;
+pub mod storage_management;
 pub mod sudo_actions;
+pub mod treasury_actions;
+pub mod voter_actions;
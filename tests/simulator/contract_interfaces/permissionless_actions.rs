@@ -0,0 +1,43 @@
+use near_sdk::serde_json::json;
+use workspaces::{result::ExecutionFinalResult, Account, Contract};
+
+pub async fn count_voting_score(
+    signer: &Account,
+    registry: &Contract,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "count_voting_score")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn refund_votes_of(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+    limit: u32,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "refund_votes_of")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "limit": limit,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn expire_voting(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "expire_voting")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -1,4 +1,10 @@
-use near_sdk::{json_types::U128, serde_json::json};
+use appchain_registry::types::{
+    RegisterDepositAsset, RegisterDepositRefundPolicy, VotingExpiryPolicy,
+};
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+};
 use workspaces::{result::ExecutionFinalResult, Account, Contract};
 
 pub async fn change_minimum_register_deposit(
@@ -13,3 +19,85 @@ pub async fn change_minimum_register_deposit(
         .transact()
         .await
 }
+
+pub async fn change_register_deposit_refund_policy(
+    signer: &Account,
+    registry: &Contract,
+    value: &RegisterDepositRefundPolicy,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "change_register_deposit_refund_policy")
+        .args_json(json!({ "value": value }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_register_deposit_asset(
+    signer: &Account,
+    registry: &Contract,
+    asset: &RegisterDepositAsset,
+    minimum_register_deposit: u128,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "set_register_deposit_asset")
+        .args_json(json!({
+            "asset": asset,
+            "minimum_register_deposit": U128::from(minimum_register_deposit)
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_audit_threshold(
+    signer: &Account,
+    registry: &Contract,
+    value: u16,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "change_audit_threshold")
+        .args_json(json!({ "value": value }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_voting_duration(
+    signer: &Account,
+    registry: &Contract,
+    value: u64,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "change_voting_duration")
+        .args_json(json!({ "value": U64::from(value) }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_voting_expiry_policy(
+    signer: &Account,
+    registry: &Contract,
+    value: VotingExpiryPolicy,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "change_voting_expiry_policy")
+        .args_json(json!({ "value": value }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_anchor_sync_stale_threshold(
+    signer: &Account,
+    registry: &Contract,
+    value: u64,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "change_anchor_sync_stale_threshold")
+        .args_json(json!({ "value": U64::from(value) }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
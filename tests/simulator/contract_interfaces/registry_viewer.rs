@@ -1,9 +1,14 @@
 use appchain_registry::types::{
-    AppchainSortingField, AppchainState, AppchainStatus, RegistryRoles, RegistrySettings,
-    SortingOrder,
+    AccountVote, AllowedAnchorMethod, AnchorFunctionCallRecord, AppchainLiveStatus,
+    AppchainSortingField, AppchainState, AppchainStateChange, AppchainStateTransition,
+    AppchainStatus, AppchainVoter, ArchivedAppchain, AuditAttestation, RegisterDepositAsset,
+    RegistryRoles, RegistrySettings, RegistryTreasury, SortingOrder,
 };
-use near_sdk::serde_json::json;
-use workspaces::Contract;
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+};
+use workspaces::{Account, Contract};
 
 pub async fn get_registry_settings(
     registry: &Contract,
@@ -27,6 +32,17 @@ pub async fn get_registry_roles(
         .json::<RegistryRoles>()
 }
 
+pub async fn get_registry_treasury(
+    registry: &Contract,
+) -> Result<RegistryTreasury, workspaces::error::Error> {
+    registry
+        .call("get_registry_treasury")
+        .view()
+        .await
+        .expect("Failed in calling 'get_registry_treasury'")
+        .json::<RegistryTreasury>()
+}
+
 pub async fn print_appchains(
     registry: &Contract,
     appchain_state: Option<Vec<AppchainState>>,
@@ -76,3 +92,242 @@ pub async fn get_appchain_status_of(
     );
     Ok(result)
 }
+
+pub async fn get_upvote_deposit_for(
+    registry: &Contract,
+    appchain_id: &String,
+    account: &Account,
+) -> anyhow::Result<U128> {
+    let result = registry
+        .call("get_upvote_deposit_for")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "account_id": account.id(),
+        }))
+        .view()
+        .await
+        .expect("Failed in calling 'get_upvote_deposit_for'")
+        .json::<U128>()
+        .expect("Failed in calling 'get_upvote_deposit_for'");
+    Ok(result)
+}
+
+pub async fn get_downvote_deposit_for(
+    registry: &Contract,
+    appchain_id: &String,
+    account: &Account,
+) -> anyhow::Result<U128> {
+    let result = registry
+        .call("get_downvote_deposit_for")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "account_id": account.id(),
+        }))
+        .view()
+        .await
+        .expect("Failed in calling 'get_downvote_deposit_for'")
+        .json::<U128>()
+        .expect("Failed in calling 'get_downvote_deposit_for'");
+    Ok(result)
+}
+
+pub async fn get_voters_of(
+    registry: &Contract,
+    appchain_id: &String,
+    from_index: u32,
+    limit: u32,
+) -> anyhow::Result<Vec<AppchainVoter>> {
+    let result = registry
+        .call("get_voters_of")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "from_index": from_index,
+            "limit": limit,
+        }))
+        .view()
+        .await
+        .expect("Failed in calling 'get_voters_of'")
+        .json::<Vec<AppchainVoter>>()
+        .expect("Failed in calling 'get_voters_of'");
+    Ok(result)
+}
+
+pub async fn get_votes_of_account(
+    registry: &Contract,
+    account: &Account,
+) -> anyhow::Result<Vec<AccountVote>> {
+    let result = registry
+        .call("get_votes_of_account")
+        .args_json(json!({
+            "account_id": account.id(),
+        }))
+        .view()
+        .await
+        .expect("Failed in calling 'get_votes_of_account'")
+        .json::<Vec<AccountVote>>()
+        .expect("Failed in calling 'get_votes_of_account'");
+    Ok(result)
+}
+
+pub async fn get_allowed_transitions(
+    registry: &Contract,
+    appchain_id: &String,
+) -> anyhow::Result<Vec<AppchainStateTransition>> {
+    let result = registry
+        .call("get_allowed_transitions")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .view()
+        .await?
+        .json::<Vec<AppchainStateTransition>>()?;
+    Ok(result)
+}
+
+pub async fn get_appchain_state_history(
+    registry: &Contract,
+    appchain_id: &String,
+    from_index: u32,
+    limit: u32,
+) -> anyhow::Result<Vec<AppchainStateChange>> {
+    let result = registry
+        .call("get_appchain_state_history")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "from_index": from_index,
+            "limit": limit,
+        }))
+        .view()
+        .await?
+        .json::<Vec<AppchainStateChange>>()?;
+    Ok(result)
+}
+
+pub async fn get_audit_attestations_of(
+    registry: &Contract,
+    appchain_id: &String,
+) -> anyhow::Result<Vec<AuditAttestation>> {
+    let result = registry
+        .call("get_audit_attestations_of")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .view()
+        .await?
+        .json::<Vec<AuditAttestation>>()?;
+    Ok(result)
+}
+
+pub async fn get_archived_appchains(
+    registry: &Contract,
+    from_index: u32,
+    limit: u32,
+) -> anyhow::Result<Vec<ArchivedAppchain>> {
+    let result = registry
+        .call("get_archived_appchains")
+        .args_json(json!({
+            "from_index": from_index,
+            "limit": limit,
+        }))
+        .view()
+        .await?
+        .json::<Vec<ArchivedAppchain>>()?;
+    Ok(result)
+}
+
+pub async fn get_archived_appchain(
+    registry: &Contract,
+    appchain_id: &String,
+    removal_index: u32,
+) -> anyhow::Result<Option<ArchivedAppchain>> {
+    let result = registry
+        .call("get_archived_appchain")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "removal_index": removal_index,
+        }))
+        .view()
+        .await?
+        .json::<Option<ArchivedAppchain>>()?;
+    Ok(result)
+}
+
+pub async fn get_anchor_template_version_of(
+    registry: &Contract,
+    appchain_id: &String,
+) -> anyhow::Result<Option<String>> {
+    let result = registry
+        .call("get_anchor_template_version_of")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .view()
+        .await?
+        .json::<Option<String>>()?;
+    Ok(result)
+}
+
+pub async fn get_anchor_function_call(
+    registry: &Contract,
+    call_id: u64,
+) -> anyhow::Result<Option<AnchorFunctionCallRecord>> {
+    let result = registry
+        .call("get_anchor_function_call")
+        .args_json(json!({ "call_id": U64::from(call_id) }))
+        .view()
+        .await?
+        .json::<Option<AnchorFunctionCallRecord>>()?;
+    Ok(result)
+}
+
+pub async fn get_allowed_anchor_methods(
+    registry: &Contract,
+) -> anyhow::Result<Vec<AllowedAnchorMethod>> {
+    let result = registry
+        .call("get_allowed_anchor_methods")
+        .view()
+        .await?
+        .json::<Vec<AllowedAnchorMethod>>()?;
+    Ok(result)
+}
+
+pub async fn get_appchain_live_status_of(
+    registry: &Contract,
+    appchain_id: &String,
+) -> anyhow::Result<Option<AppchainLiveStatus>> {
+    let result = registry
+        .call("get_appchain_live_status_of")
+        .args_json(json!({ "appchain_id": appchain_id }))
+        .view()
+        .await?
+        .json::<Option<AppchainLiveStatus>>()?;
+    Ok(result)
+}
+
+pub async fn get_stale_appchains(
+    registry: &Contract,
+    from_index: u32,
+    limit: u32,
+) -> anyhow::Result<Vec<AppchainStatus>> {
+    let result = registry
+        .call("get_stale_appchains")
+        .args_json(json!({
+            "from_index": from_index,
+            "limit": limit,
+        }))
+        .view()
+        .await?
+        .json::<Vec<AppchainStatus>>()?;
+    Ok(result)
+}
+
+pub async fn get_claimable_register_deposit_of(
+    registry: &Contract,
+    account: &Account,
+    asset: &RegisterDepositAsset,
+) -> anyhow::Result<U128> {
+    let result = registry
+        .call("get_claimable_register_deposit_of")
+        .args_json(json!({
+            "account_id": account.id(),
+            "asset": asset,
+        }))
+        .view()
+        .await?
+        .json::<U128>()?;
+    Ok(result)
+}
@@ -0,0 +1,33 @@
+use near_contract_standards::storage_management::StorageBalance;
+use near_sdk::serde_json::json;
+use workspaces::{result::ExecutionFinalResult, Account, Contract};
+
+pub async fn storage_deposit(
+    signer: &Account,
+    registry: &Contract,
+    amount: u128,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "storage_deposit")
+        .args_json(json!({
+            "account_id": Option::<String>::None,
+            "registration_only": Option::<bool>::None,
+        }))
+        .deposit(amount)
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn storage_balance_of(
+    registry: &Contract,
+    account: &Account,
+) -> Result<Option<StorageBalance>, workspaces::error::Error> {
+    registry
+        .call("storage_balance_of")
+        .args_json(json!({ "account_id": account.id() }))
+        .view()
+        .await
+        .expect("Failed in calling 'storage_balance_of'")
+        .json::<Option<StorageBalance>>()
+}
@@ -1,4 +1,6 @@
-use workspaces::{result::ExecutionFinalResult, Account, Contract};
+use appchain_registry::types::{AppchainState, AppchainType};
+use near_sdk::{json_types::U64, serde_json::json};
+use workspaces::{result::ExecutionFinalResult, Account, AccountId, Contract};
 
 pub async fn pause_asset_transfer(
     signer: &Account,
@@ -21,3 +23,65 @@ pub async fn resume_asset_transfer(
         .transact()
         .await
 }
+
+pub async fn set_treasury_account(
+    signer: &Account,
+    registry: &Contract,
+    account_id: &AccountId,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "set_treasury_account")
+        .args_json(json!({ "account_id": account_id }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_contract_code_staging_duration(
+    signer: &Account,
+    registry: &Contract,
+    duration_in_seconds: u64,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "set_contract_code_staging_duration")
+        .args_json(json!({ "duration_in_seconds": U64::from(duration_in_seconds) }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn force_change_appchain_state(
+    signer: &Account,
+    registry: &Contract,
+    appchain_id: &String,
+    state: AppchainState,
+    reason: Option<&str>,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "force_change_appchain_state")
+        .args_json(json!({
+            "appchain_id": appchain_id,
+            "state": state,
+            "reason": reason,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn allow_anchor_method(
+    signer: &Account,
+    registry: &Contract,
+    method_name: &str,
+    appchain_types: Option<Vec<AppchainType>>,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "allow_anchor_method")
+        .args_json(json!({
+            "method_name": method_name,
+            "appchain_types": appchain_types,
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -0,0 +1,20 @@
+use appchain_registry::types::RegisterDepositAsset;
+use near_sdk::{json_types::U128, serde_json::json};
+use workspaces::{result::ExecutionFinalResult, Account, Contract};
+
+pub async fn sweep_treasury(
+    signer: &Account,
+    registry: &Contract,
+    asset: &RegisterDepositAsset,
+    amount: Option<u128>,
+) -> Result<ExecutionFinalResult, workspaces::error::Error> {
+    signer
+        .call(registry.id(), "sweep_treasury")
+        .args_json(json!({
+            "asset": asset,
+            "amount": amount.map(U128::from)
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -5,4 +5,8 @@ mod test_case1;
 mod test_case2;
 mod test_case3;
 mod test_case4;
+mod test_case5;
+mod test_case6;
+mod test_case7;
+mod test_case8;
 mod test_case9;
@@ -2,11 +2,13 @@ use crate::{
     common,
     contract_interfaces::{
         appchain_lifecycle_manager, appchain_owner_actions, registry_settings, registry_viewer,
-        sudo_actions,
+        storage_management, sudo_actions, treasury_actions,
     },
 };
 use appchain_registry::types::{
-    AppchainSortingField, AppchainState, SubstrateTemplateType, SortingOrder,
+    AppchainRejectionCode, AppchainSortingField, AppchainState, AppchainStateActor,
+    AppchainStateTransition, RegisterDepositAsset, RegisterDepositRefundCase,
+    RegisterDepositRefundPolicy, SortingOrder, SubstrateTemplateType,
 };
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::{json_types::U128, AccountId};
@@ -110,6 +112,54 @@ async fn test_case1() -> anyhow::Result<()> {
         common::get_ft_balance_of(&users[0], &oct_token).await?.0,
         total_supply / 10
     );
+    // The register deposit is returned if the fungible token metadata is invalid.
+    //
+    assert!(appchain_owner_actions::register_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        Some("appchain1 description".to_string()),
+        Some(SubstrateTemplateType::Barnacle),
+        Some("http://ddfs.dsdfs".to_string()),
+        Some("https://jldfs.yoasdfasd".to_string()),
+        Some("joe@lksdf.com".to_string()),
+        Some(AccountId::from_str(users[1].id().as_str()).unwrap()),
+        Some(U128::from(10000000)),
+        Some(U128::from(10000000)),
+        Some(U128::from(1000000)),
+        Some(U128::from(100)),
+        Some(FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "joeToken".to_string(),
+            symbol: "JOT".to_string(),
+            icon: Option::None,
+            reference: Some("https://jldfs.yoasdfasd/token.json".to_string()),
+            reference_hash: Option::None,
+            decimals: 18,
+        }),
+        Some(HashMap::from([("key1".to_string(), "value1".to_string())])),
+        common::to_oct_amount(1200),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert_eq!(
+        registry_viewer::print_appchains(
+            &registry,
+            Option::None,
+            1,
+            5,
+            AppchainSortingField::AppchainId,
+            SortingOrder::Ascending
+        )
+        .await?,
+        0
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        total_supply / 10
+    );
     //
     let amount = common::to_oct_amount(1200);
     assert!(sudo_actions::pause_asset_transfer(&root, &registry)
@@ -167,6 +217,17 @@ async fn test_case1() -> anyhow::Result<()> {
         .await
         .unwrap()
         .is_success());
+    let storage_available_of_user0 = storage_management::storage_balance_of(&registry, &users[0])
+        .await?
+        .unwrap()
+        .available
+        .0;
+    let storage_available_of_user1 = storage_management::storage_balance_of(&registry, &users[1])
+        .await?
+        .unwrap()
+        .available
+        .0;
+    let amount = common::to_oct_amount(1500);
     assert!(appchain_owner_actions::register_appchain(
         &users[0],
         &oct_token,
@@ -209,6 +270,22 @@ async fn test_case1() -> anyhow::Result<()> {
     );
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Registered);
+    assert_eq!(appchain.register_deposit.0, common::to_oct_amount(1200));
+    let transitions = registry_viewer::get_allowed_transitions(&registry, &appchain_id).await?;
+    assert_eq!(transitions.len(), 3);
+    assert!(transitions.contains(&AppchainStateTransition {
+        from: AppchainState::Registered,
+        to: AppchainState::Closed,
+        actor: AppchainStateActor::AppchainOwner,
+    }));
+    assert!(
+        storage_management::storage_balance_of(&registry, &users[0])
+            .await?
+            .unwrap()
+            .available
+            .0
+            < storage_available_of_user0
+    );
     //
     assert!(appchain_owner_actions::transfer_appchain_ownership(
         &users[1],
@@ -228,6 +305,22 @@ async fn test_case1() -> anyhow::Result<()> {
     .await
     .unwrap()
     .is_success());
+    assert_eq!(
+        storage_management::storage_balance_of(&registry, &users[0])
+            .await?
+            .unwrap()
+            .available
+            .0,
+        storage_available_of_user0
+    );
+    assert!(
+        storage_management::storage_balance_of(&registry, &users[1])
+            .await?
+            .unwrap()
+            .available
+            .0
+            < storage_available_of_user1
+    );
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
     assert_eq!(
         &appchain.appchain_owner,
@@ -292,41 +385,210 @@ async fn test_case1() -> anyhow::Result<()> {
         .eq("yangzhen@oct.network"));
     assert!(appchain.appchain_metadata.custom_metadata.keys().len() == 1);
     //
+    let refund_policy = RegisterDepositRefundPolicy {
+        withdrawn_while_registered_percent: 80,
+        withdrawn_after_audit_percent: 60,
+        rejected_percent: 50,
+        went_live_percent: 100,
+    };
+    assert!(registry_settings::change_register_deposit_refund_policy(
+        &users[0],
+        &registry,
+        &refund_policy
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(registry_settings::change_register_deposit_refund_policy(
+        &root,
+        &registry,
+        &refund_policy
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(appchain_owner_actions::claim_register_deposit_refund(
+        &users[1],
+        &registry,
+        &appchain_id
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    //
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &users[4],
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing."
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let history =
+        registry_viewer::get_appchain_state_history(&registry, &appchain_id, 0, 50).await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from, AppchainState::Registered);
+    assert_eq!(history[0].to, AppchainState::Closed);
+    assert_eq!(history[0].actor.as_str(), root.id().as_str());
+    assert!(!history[0].forced);
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    let rejection = appchain.rejection.unwrap();
+    assert_eq!(rejection.code, AppchainRejectionCode::FailedAuditing);
+    assert_eq!(rejection.reason, "Failed in auditing.");
+    assert_eq!(history[0].reason, Some(rejection.reason));
+    assert_eq!(
+        common::get_ft_balance_of(&users[1], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&registry.as_account(), &oct_token)
+            .await?
+            .0,
+        common::to_oct_amount(1200)
+    );
+    //
+    assert!(appchain_owner_actions::claim_register_deposit_refund(
+        &users[0],
+        &registry,
+        &appchain_id
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(appchain_owner_actions::claim_register_deposit_refund(
+        &users[1],
+        &registry,
+        &appchain_id
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(appchain_owner_actions::claim_register_deposit_refund(
+        &users[1],
+        &registry,
+        &appchain_id
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(
+        appchain.register_deposit_refund_case,
+        Some(RegisterDepositRefundCase::Rejected)
+    );
+    assert_eq!(
+        appchain.refunded_register_deposit.unwrap().0,
+        common::to_oct_amount(600)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&users[1], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10 + 600)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&registry.as_account(), &oct_token)
+            .await?
+            .0,
+        common::to_oct_amount(600)
+    );
+    //
+    let oct_asset =
+        RegisterDepositAsset::FungibleToken(AccountId::from_str(oct_token.id().as_str()).unwrap());
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(treasury.ledgers.len(), 1);
+    let treasury = treasury.ledgers[0].clone();
+    assert_eq!(treasury.asset, oct_asset);
+    assert_eq!(treasury.pending_register_deposits.0, 0);
+    assert_eq!(treasury.reserved_register_deposit_refunds.0, 0);
+    assert_eq!(treasury.withdrawable_fees.0, common::to_oct_amount(600));
+    assert!(
+        treasury_actions::sweep_treasury(&root, &registry, &oct_asset, None)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    assert!(
+        sudo_actions::set_treasury_account(&users[2], &registry, users[3].id())
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    assert!(
+        sudo_actions::set_treasury_account(&root, &registry, users[3].id())
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let treasury_balance = common::get_ft_balance_of(&users[3], &oct_token).await?.0;
     assert!(
-        appchain_lifecycle_manager::reject_appchain(&users[4], &registry, &appchain_id)
+        treasury_actions::sweep_treasury(&users[2], &registry, &oct_asset, None)
             .await
             .unwrap()
             .is_failure()
     );
+    assert!(treasury_actions::sweep_treasury(
+        &root,
+        &registry,
+        &oct_asset,
+        Some(common::to_oct_amount(601))
+    )
+    .await
+    .unwrap()
+    .is_failure());
     assert!(
-        appchain_lifecycle_manager::reject_appchain(&root, &registry, &appchain_id)
+        treasury_actions::sweep_treasury(&root, &registry, &oct_asset, None)
             .await
             .unwrap()
             .is_success()
     );
+    let treasury = registry_viewer::get_registry_treasury(&registry)
+        .await?
+        .ledgers[0]
+        .clone();
+    assert_eq!(treasury.withdrawable_fees.0, 0);
+    assert_eq!(treasury.swept_fees.0, common::to_oct_amount(600));
     assert_eq!(
-        common::get_ft_balance_of(&users[1], &oct_token).await?.0,
-        common::to_oct_amount(TOTAL_SUPPLY / 10)
+        common::get_ft_balance_of(&users[3], &oct_token).await?.0,
+        treasury_balance + common::to_oct_amount(600)
     );
     assert_eq!(
         common::get_ft_balance_of(&registry.as_account(), &oct_token)
             .await?
             .0,
-        common::to_oct_amount(1200)
+        0
     );
     //
     assert!(
-        appchain_lifecycle_manager::remove_appchain(&users[2], &registry, &appchain_id)
+        appchain_lifecycle_manager::remove_appchain(&users[2], &registry, &appchain_id, false)
             .await
             .unwrap()
             .is_failure()
     );
     assert!(
-        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id)
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
             .await
             .unwrap()
             .is_success()
     );
+    assert_eq!(
+        storage_management::storage_balance_of(&registry, &users[1])
+            .await?
+            .unwrap()
+            .available
+            .0,
+        storage_available_of_user1
+    );
     assert_eq!(
         registry_viewer::print_appchains(
             &registry,
@@ -339,5 +601,18 @@ async fn test_case1() -> anyhow::Result<()> {
         .await?,
         0
     );
+    let archived_appchain = registry_viewer::get_archived_appchain(&registry, &appchain_id, 0)
+        .await?
+        .unwrap();
+    assert_eq!(archived_appchain.removal_index, 0);
+    assert_eq!(archived_appchain.final_state, AppchainState::Closed);
+    assert_eq!(
+        archived_appchain.appchain_owner.as_str(),
+        users[1].id().as_str()
+    );
+    assert!(archived_appchain.appchain_anchor.is_none());
+    let archived_appchains = registry_viewer::get_archived_appchains(&registry, 0, 50).await?;
+    assert_eq!(archived_appchains.len(), 1);
+    assert_eq!(archived_appchains[0].appchain_id, appchain_id);
     Ok(())
 }
@@ -1,15 +1,19 @@
 use crate::{
     common,
     contract_interfaces::{
-        appchain_lifecycle_manager, appchain_owner_actions, registry_roles, registry_viewer,
-        sudo_actions,
+        anchor_template_manager, appchain_lifecycle_manager, appchain_owner_actions,
+        permissionless_actions, registry_roles, registry_settings, registry_viewer, sudo_actions,
+        voter_actions,
     },
 };
 use appchain_registry::types::{
-    AppchainSortingField, AppchainState, SubstrateTemplateType, SortingOrder,
+    AnchorDeploymentStatus, AnchorUpgradeStatus, AppchainSortingField, AppchainState, AppchainType,
+    AuditReport, MultiTxsOperationProcessingResult, SortingOrder, SubstrateTemplateType,
+    VotingExpiryPolicy,
 };
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_sdk::{json_types::U128, AccountId};
+use near_units::parse_near;
 use std::{collections::HashMap, str::FromStr};
 
 const TOTAL_SUPPLY: u128 = 100_000_000;
@@ -149,7 +153,8 @@ async fn test_case2() -> anyhow::Result<()> {
     assert!(appchain_lifecycle_manager::pass_auditing_appchain(
         &users[0],
         &registry,
-        &appchain_id1
+        &appchain_id1,
+        None
     )
     .await
     .unwrap()
@@ -157,20 +162,34 @@ async fn test_case2() -> anyhow::Result<()> {
     assert!(appchain_lifecycle_manager::pass_auditing_appchain(
         &users[1],
         &registry,
-        &appchain_id1
+        &appchain_id1,
+        None
     )
     .await
     .unwrap()
     .is_failure());
     //
-    assert!(
-        appchain_lifecycle_manager::pass_auditing_appchain(&root, &registry, &appchain_id1)
-            .await
-            .unwrap()
-            .is_success()
-    );
+    let audit_report = AuditReport {
+        report_url: "https://example.com/audit_report.pdf".to_string(),
+        content_hash: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+            .to_string(),
+        auditor: AccountId::from_str(root.id().as_str()).unwrap(),
+        notes: "No critical issue found.".to_string(),
+    };
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id1,
+        Some(audit_report.clone())
+    )
+    .await
+    .unwrap()
+    .is_success());
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
+    let saved_report = appchain.audit_report.unwrap();
+    assert_eq!(saved_report.report_url, audit_report.report_url);
+    assert_eq!(saved_report.content_hash, audit_report.content_hash);
     assert!(
         appchain_lifecycle_manager::start_voting_appchain(&root, &registry, &appchain_id1)
             .await
@@ -180,14 +199,23 @@ async fn test_case2() -> anyhow::Result<()> {
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Voting);
     //
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id2,
+        None
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
     assert!(
-        appchain_lifecycle_manager::pass_auditing_appchain(&root, &registry, &appchain_id2)
+        registry_settings::change_voting_duration(&root, &registry, 1)
             .await
             .unwrap()
             .is_success()
     );
-    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
-    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
     assert!(
         appchain_lifecycle_manager::start_voting_appchain(&root, &registry, &appchain_id2)
             .await
@@ -197,14 +225,23 @@ async fn test_case2() -> anyhow::Result<()> {
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Voting);
     //
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id3,
+        None
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
     assert!(
-        appchain_lifecycle_manager::pass_auditing_appchain(&root, &registry, &appchain_id3)
+        registry_settings::change_voting_duration(&root, &registry, 3600 * 24 * 30)
             .await
             .unwrap()
             .is_success()
     );
-    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
-    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
     assert!(
         appchain_lifecycle_manager::start_voting_appchain(&root, &registry, &appchain_id3)
             .await
@@ -214,6 +251,25 @@ async fn test_case2() -> anyhow::Result<()> {
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Voting);
     //
+    // The votes for appchain2 are refused after its voting deadline.
+    //
+    assert!(voter_actions::downvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id2,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
+    assert_eq!(appchain.downvote_deposit.0, 0);
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10)
+    );
+    //
     assert_eq!(
         registry_viewer::print_appchains(
             &registry,
@@ -231,15 +287,161 @@ async fn test_case2() -> anyhow::Result<()> {
         .await
         .unwrap()
         .is_success());
+    voter_actions::upvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id1,
+        common::to_oct_amount(200),
+    )
+    .await
+    .unwrap();
+    voter_actions::downvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id3,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap();
     let appchain1 = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
-    let appchain2 = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
+    let appchain3 = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
     assert_eq!(appchain1.upvote_deposit.0, 0);
-    assert_eq!(appchain2.downvote_deposit.0, 0);
+    assert_eq!(appchain3.downvote_deposit.0, 0);
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10)
+    );
     assert!(sudo_actions::resume_asset_transfer(&root, &registry)
         .await
         .unwrap()
         .is_success());
     //
+    assert!(voter_actions::upvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id1,
+        common::to_oct_amount(200),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::upvote_appchain(
+        &users[4],
+        &oct_token,
+        &registry,
+        &appchain_id1,
+        common::to_oct_amount(300),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::downvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id3,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain1 = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
+    let appchain3 = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
+    assert_eq!(appchain1.upvote_deposit.0, common::to_oct_amount(500));
+    assert_eq!(appchain3.downvote_deposit.0, common::to_oct_amount(100));
+    assert_eq!(
+        registry_viewer::get_upvote_deposit_for(&registry, &appchain_id1, &users[0])
+            .await?
+            .0,
+        common::to_oct_amount(200)
+    );
+    assert_eq!(
+        registry_viewer::get_downvote_deposit_for(&registry, &appchain_id3, &users[0])
+            .await?
+            .0,
+        common::to_oct_amount(100)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10 - 300)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&registry.as_account(), &oct_token)
+            .await?
+            .0,
+        common::to_oct_amount(3600)
+    );
+    let voters = registry_viewer::get_voters_of(&registry, &appchain_id1, 0, 10).await?;
+    assert_eq!(voters.len(), 2);
+    let voters = registry_viewer::get_voters_of(&registry, &appchain_id1, 1, 10).await?;
+    assert_eq!(voters.len(), 1);
+    let votes = registry_viewer::get_votes_of_account(&registry, &users[0]).await?;
+    assert_eq!(votes.len(), 2);
+    //
+    assert!(voter_actions::withdraw_upvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id1,
+        common::to_oct_amount(300),
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(voter_actions::withdraw_upvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id1,
+        common::to_oct_amount(50),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::withdraw_downvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id3,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain1 = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
+    let appchain3 = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
+    assert_eq!(appchain1.upvote_deposit.0, common::to_oct_amount(450));
+    assert_eq!(appchain3.downvote_deposit.0, 0);
+    let voters = registry_viewer::get_voters_of(&registry, &appchain_id3, 0, 10).await?;
+    assert_eq!(voters.len(), 0);
+    let votes = registry_viewer::get_votes_of_account(&registry, &users[0]).await?;
+    assert_eq!(votes.len(), 1);
+    assert_eq!(&votes[0].appchain_id, &appchain_id1);
+    assert_eq!(votes[0].upvote_deposit.0, common::to_oct_amount(150));
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10 - 150)
+    );
+    //
+    let result = permissionless_actions::count_voting_score(&users[2], &registry)
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        result.json::<MultiTxsOperationProcessingResult>()?,
+        MultiTxsOperationProcessingResult::Ok
+    );
+    let appchain1 = registry_viewer::get_appchain_status_of(&registry, &appchain_id1).await?;
+    let appchain2 = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
+    assert_eq!(appchain1.voting_score.0, common::to_oct_amount(450) as i128);
+    assert_eq!(appchain2.voting_score.0, 0);
+    assert!(
+        permissionless_actions::count_voting_score(&users[2], &registry)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    //
     assert!(
         appchain_lifecycle_manager::start_booting_appchain(&root, &registry, &appchain_id3)
             .await
@@ -266,6 +468,48 @@ async fn test_case2() -> anyhow::Result<()> {
             .unwrap()
             .is_success()
     );
+    //
+    // Register two versions of anchor template, and deprecate the latest one.
+    //
+    let anchor_wasm = std::fs::read(format!("res/mock_appchain_anchor.wasm"))?;
+    let barnacle = AppchainType::Substrate(SubstrateTemplateType::Barnacle);
+    for version in ["1.0.0", "1.1.0"] {
+        assert!(anchor_template_manager::store_wasm_of_appchain_anchor(
+            &root,
+            &registry,
+            anchor_wasm.clone()
+        )
+        .await
+        .unwrap()
+        .is_success());
+        assert!(anchor_template_manager::register_anchor_template(
+            &root,
+            &registry,
+            barnacle.clone(),
+            version
+        )
+        .await
+        .unwrap()
+        .is_success());
+    }
+    assert!(anchor_template_manager::register_anchor_template(
+        &root,
+        &registry,
+        barnacle.clone(),
+        "1.2"
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(anchor_template_manager::deprecate_anchor_template(
+        &root,
+        &registry,
+        barnacle.clone(),
+        "1.1.0"
+    )
+    .await
+    .unwrap()
+    .is_success());
     assert!(
         appchain_lifecycle_manager::start_booting_appchain(&council, &registry, &appchain_id3)
             .await
@@ -274,6 +518,103 @@ async fn test_case2() -> anyhow::Result<()> {
     );
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Booting);
+    assert_eq!(
+        appchain.anchor_deployment_status,
+        Some(AnchorDeploymentStatus::Deployed)
+    );
+    assert_eq!(
+        registry_viewer::get_anchor_template_version_of(&registry, &appchain_id3).await?,
+        Some("1.0.0".to_string())
+    );
+    //
+    // Push a new anchor template to the anchor of appchain3.
+    //
+    assert!(anchor_template_manager::store_wasm_of_appchain_anchor(
+        &root,
+        &registry,
+        anchor_wasm.clone()
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(anchor_template_manager::register_anchor_template(
+        &root,
+        &registry,
+        barnacle.clone(),
+        "1.2.0"
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(anchor_template_manager::upgrade_appchain_anchors(
+        &root,
+        &registry,
+        vec![appchain_id3.clone()],
+        None,
+        parse_near!("3 N")
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(
+        sudo_actions::set_contract_code_staging_duration(&root, &registry, 0)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    assert!(anchor_template_manager::upgrade_appchain_anchors(
+        &users[0],
+        &registry,
+        vec![appchain_id3.clone()],
+        None,
+        parse_near!("3 N")
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(anchor_template_manager::upgrade_appchain_anchors(
+        &root,
+        &registry,
+        vec![appchain_id3.clone()],
+        None,
+        0
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(anchor_template_manager::upgrade_appchain_anchors(
+        &root,
+        &registry,
+        vec![appchain_id3.clone()],
+        None,
+        parse_near!("3 N")
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
+    assert_eq!(
+        appchain.anchor_upgrade.unwrap().status,
+        AnchorUpgradeStatus::Upgraded
+    );
+    assert_eq!(appchain.anchor_template_version, Some("1.2.0".to_string()));
+    //
+    assert!(voter_actions::upvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id3,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id3).await?;
+    assert_eq!(appchain.upvote_deposit.0, 0);
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10 - 150)
+    );
     //
     assert_eq!(
         registry_viewer::print_appchains(
@@ -300,5 +641,69 @@ async fn test_case2() -> anyhow::Result<()> {
         1
     );
     //
+    // Expire the voting of appchains.
+    //
+    assert!(
+        permissionless_actions::expire_voting(&users[0], &registry, &appchain_id1)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    assert!(registry_settings::change_voting_expiry_policy(
+        &root,
+        &registry,
+        VotingExpiryPolicy::BackToAudited
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        permissionless_actions::expire_voting(&users[0], &registry, &appchain_id2)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id2).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
+    assert!(appchain.voting_deadline.is_none());
+    //
+    // Remove appchain3, its anchor account is deleted.
+    //
+    assert!(sudo_actions::force_change_appchain_state(
+        &root,
+        &registry,
+        &appchain_id3,
+        AppchainState::Closed,
+        Some("Testing the removal of anchor.")
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        registry_viewer::get_allowed_transitions(&registry, &appchain_id3)
+            .await?
+            .is_empty()
+    );
+    assert!(
+        permissionless_actions::refund_votes_of(&users[0], &registry, &appchain_id3, 10)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id3, true)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    assert!(
+        registry_viewer::get_archived_appchain(&registry, &appchain_id3, 0)
+            .await?
+            .is_some()
+    );
+    let anchor_account_id: workspaces::AccountId =
+        format!("{}.{}", appchain_id3, registry.id()).parse()?;
+    assert!(worker.view_account(&anchor_account_id).await.is_err());
+    //
     Ok(())
 }
@@ -55,12 +55,15 @@ async fn test_case3() -> anyhow::Result<()> {
         let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
         assert_eq!(&appchain.appchain_state, &AppchainState::Registered);
         //
-        assert!(
-            appchain_lifecycle_manager::pass_auditing_appchain(&root, &registry, &appchain_id)
-                .await
-                .unwrap()
-                .is_success()
-        );
+        assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+            &root,
+            &registry,
+            &appchain_id,
+            None
+        )
+        .await
+        .unwrap()
+        .is_success());
         let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
         assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
         assert!(
@@ -1,15 +1,21 @@
 use crate::{
     common,
     contract_interfaces::{
-        appchain_lifecycle_manager, appchain_owner_actions, registry_roles, registry_viewer,
+        appchain_lifecycle_manager, appchain_owner_actions, registry_roles, registry_settings,
+        registry_viewer, sudo_actions,
     },
 };
 use appchain_anchor::types::ProtocolSettings;
 use appchain_registry::types::{
-    AppchainSortingField, AppchainState, SubstrateTemplateType, SortingOrder,
+    AnchorFunctionCallStatus, AppchainHealth, AppchainSortingField, AppchainState, AppchainType,
+    SortingOrder, SubstrateTemplateType,
 };
 use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
-use near_sdk::{json_types::U128, serde_json::json, AccountId};
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+    AccountId,
+};
 use std::{collections::HashMap, str::FromStr};
 use workspaces::Account;
 
@@ -66,12 +72,15 @@ async fn test_case4() -> anyhow::Result<()> {
         .await?,
         1
     );
-    assert!(
-        appchain_lifecycle_manager::pass_auditing_appchain(&root, &registry, &appchain_id)
-            .await
-            .unwrap()
-            .is_success()
-    );
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        None
+    )
+    .await
+    .unwrap()
+    .is_success());
     let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
     assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
     assert!(
@@ -127,6 +136,59 @@ async fn test_case4() -> anyhow::Result<()> {
         .json::<ProtocolSettings>()
         .unwrap();
     assert!(new_protocol_settings.minimum_validator_deposit.0 == 5000000000000000000000 as u128);
+    //
+    // Only the methods in the allow-list can be called.
+    //
+    let change_minimum_validator_deposit = json!({
+        "appchain_id": "appchain1",
+        "function_name": "change_minimum_validator_deposit",
+        "args": "{
+            \"value\": \"6000000000000000000000\"
+        }"
+    });
+    assert!(council
+        .call(registry.id(), "call_anchor_function")
+        .args_json(change_minimum_validator_deposit.clone())
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .expect("Failed to call function 'call_anchor_function'.")
+        .is_failure());
+    assert!(sudo_actions::allow_anchor_method(
+        &root,
+        &registry,
+        "change_minimum_validator_deposit",
+        Some(vec![AppchainType::Cosmos])
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(council
+        .call(registry.id(), "call_anchor_function")
+        .args_json(change_minimum_validator_deposit.clone())
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .expect("Failed to call function 'call_anchor_function'.")
+        .is_failure());
+    for method_name in [
+        "change_minimum_validator_deposit",
+        "get_protocol_settings",
+        "function_not_exists",
+    ] {
+        assert!(
+            sudo_actions::allow_anchor_method(&root, &registry, method_name, None)
+                .await
+                .unwrap()
+                .is_success()
+        );
+    }
+    assert_eq!(
+        registry_viewer::get_allowed_anchor_methods(&registry)
+            .await?
+            .len(),
+        3
+    );
     let result = council
         .call(registry.id(), "call_anchor_function")
         .args_json(json!({
@@ -149,6 +211,185 @@ async fn test_case4() -> anyhow::Result<()> {
         .json::<ProtocolSettings>()
         .unwrap();
     assert!(new_protocol_settings.minimum_validator_deposit.0 == 6000000000000000000000 as u128);
+    let call = registry_viewer::get_anchor_function_call(&registry, 0)
+        .await?
+        .unwrap();
+    assert_eq!(call.status, AnchorFunctionCallStatus::Succeeded);
+    //
+    let result = council
+        .call(registry.id(), "batch_call_anchor_functions")
+        .args_json(json!({
+            "calls": [
+                {
+                    "appchain_id": "appchain1",
+                    "function_name": "get_protocol_settings",
+                    "args": "{}",
+                    "deposit": "0",
+                },
+                {
+                    "appchain_id": "appchain1",
+                    "function_name": "function_not_exists",
+                    "args": "{}",
+                    "deposit": "0",
+                    "gas": "20000000000000",
+                },
+            ]
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .expect("Failed to call function 'batch_call_anchor_functions'.");
+    println!(
+        "Result of calling 'batch_call_anchor_functions': {:?}",
+        result
+    );
+    assert_eq!(result.json::<Vec<U64>>()?, vec![U64::from(1), U64::from(2)]);
+    let call = registry_viewer::get_anchor_function_call(&registry, 1)
+        .await?
+        .unwrap();
+    assert_eq!(call.status, AnchorFunctionCallStatus::Succeeded);
+    assert_eq!(
+        near_sdk::serde_json::from_slice::<ProtocolSettings>(&call.return_value.unwrap().0)?
+            .minimum_validator_deposit
+            .0,
+        6000000000000000000000
+    );
+    let call = registry_viewer::get_anchor_function_call(&registry, 2)
+        .await?
+        .unwrap();
+    assert_eq!(call.status, AnchorFunctionCallStatus::Failed);
+    //
+    // Sync the status of appchain by anchor, with both the legacy and the versioned payload.
+    //
+    assert!(users[0]
+        .call(registry.id(), "sync_state_of")
+        .args_json(json!({
+            "appchain_id": "appchain1",
+            "appchain_state": AppchainState::Active,
+            "validator_count": 4,
+            "total_stake": U128::from(1000),
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .unwrap()
+        .is_failure());
+    assert!(anchor
+        .as_account()
+        .call(registry.id(), "sync_state_of")
+        .args_json(json!({
+            "appchain_id": "appchain1",
+            "appchain_state": AppchainState::Active,
+            "validator_count": 4,
+            "total_stake": U128::from(1000),
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .unwrap()
+        .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Active);
+    assert_eq!(appchain.validator_count, 4);
+    assert!(
+        registry_viewer::get_appchain_live_status_of(&registry, &appchain_id)
+            .await?
+            .is_none()
+    );
+    assert!(anchor
+        .as_account()
+        .call(registry.id(), "sync_status_of")
+        .args_json(json!({
+            "appchain_id": "appchain1",
+            "payload": {
+                "V1": {
+                    "appchain_state": AppchainState::Active,
+                    "validator_count": 5,
+                    "total_stake": U128::from(2000),
+                    "era_number": U64::from(12),
+                    "last_era_reward": U128::from(300),
+                    "total_bridged_token_supply": U128::from(50000),
+                    "anchor_version": "v2.5.0",
+                    "appchain_block_height": U64::from(86400),
+                }
+            }
+        }))
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+        .unwrap()
+        .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.validator_count, 5);
+    assert_eq!(appchain.total_stake.0, 2000);
+    let live_status = registry_viewer::get_appchain_live_status_of(&registry, &appchain_id)
+        .await?
+        .unwrap();
+    assert_eq!(live_status.era_number.0, 12);
+    assert_eq!(live_status.last_era_reward.0, 300);
+    assert_eq!(live_status.total_bridged_token_supply.0, 50000);
+    assert_eq!(live_status.anchor_version, "v2.5.0".to_string());
+    assert_eq!(live_status.appchain_block_height.0, 86400);
+    //
+    // Detect the stale appchains by the time of the last sync from anchor.
+    //
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert!(appchain.last_sync_time.is_some());
+    assert_eq!(appchain.health, Some(AppchainHealth::Healthy));
+    assert!(registry_viewer::get_stale_appchains(&registry, 0, 50)
+        .await?
+        .is_empty());
+    assert!(
+        registry_settings::change_anchor_sync_stale_threshold(&users[0], &registry, 1)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    assert!(
+        registry_settings::change_anchor_sync_stale_threshold(&root, &registry, 1)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let stale_appchains = registry_viewer::get_stale_appchains(&registry, 0, 50).await?;
+    assert_eq!(stale_appchains.len(), 1);
+    assert_eq!(stale_appchains[0].appchain_id, appchain_id);
+    assert_eq!(stale_appchains[0].health, Some(AppchainHealth::Stale));
+    assert!(
+        registry_settings::change_anchor_sync_stale_threshold(&root, &registry, 0)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert!(appchain.health.is_none());
+    assert!(registry_viewer::get_stale_appchains(&registry, 0, 50)
+        .await?
+        .is_empty());
+    //
+    // The anchor closes the appchain.
+    //
+    for (appchain_state, expected_state) in [
+        (AppchainState::Closed, AppchainState::Active),
+        (AppchainState::Closing, AppchainState::Closing),
+        (AppchainState::Closed, AppchainState::Closed),
+    ] {
+        anchor
+            .as_account()
+            .call(registry.id(), "sync_state_of")
+            .args_json(json!({
+                "appchain_id": "appchain1",
+                "appchain_state": appchain_state,
+                "validator_count": 0,
+                "total_stake": U128::from(0),
+            }))
+            .gas(200_000_000_000_000)
+            .transact()
+            .await
+            .unwrap();
+        let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+        assert_eq!(appchain.appchain_state, expected_state);
+    }
     //
     Ok(())
 }
@@ -0,0 +1,224 @@
+use crate::{
+    common,
+    contract_interfaces::{
+        appchain_lifecycle_manager, appchain_owner_actions, registry_viewer, voter_actions,
+    },
+};
+use appchain_registry::types::{AppchainRejectionCode, AppchainState, SubstrateTemplateType};
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
+use near_sdk::{json_types::U128, serde_json::json, AccountId};
+use std::{collections::HashMap, str::FromStr};
+
+const TOTAL_SUPPLY: u128 = 100_000_000;
+
+/// Test restoring voter deposits when the `ft_transfer` of withdrawal fails.
+#[tokio::test]
+async fn test_case5() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let total_supply = common::to_oct_amount(TOTAL_SUPPLY);
+    let (root, oct_token, registry, _council, users) =
+        common::basic_actions::initialize_contracts_and_users(&worker, total_supply, false).await?;
+    //
+    let appchain_id = "test_appchain1".to_string();
+    let amount = common::to_oct_amount(1000);
+    appchain_owner_actions::register_appchain(
+        &users[1],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        Some("appchain1 description".to_string()),
+        Some(SubstrateTemplateType::Barnacle),
+        Some("http://ddfs.dsdfs".to_string()),
+        Some("https://jldfs.yoasdfasd".to_string()),
+        Some("joe@lksdf.com".to_string()),
+        Some(AccountId::from_str(users[1].id().as_str()).unwrap()),
+        Some(U128::from(10000000)),
+        Some(U128::from(10000000)),
+        Some(U128::from(1000000)),
+        Some(U128::from(100)),
+        Some(FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "joeToken".to_string(),
+            symbol: "JOT".to_string(),
+            icon: Option::None,
+            reference: Option::None,
+            reference_hash: Option::None,
+            decimals: 18,
+        }),
+        Some(HashMap::from([("key1".to_string(), "value1".to_string())])),
+        amount,
+    )
+    .await
+    .expect("Failed in calling 'register_appchain'")
+    .unwrap();
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        None
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        appchain_lifecycle_manager::start_voting_appchain(&root, &registry, &appchain_id)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Voting);
+    //
+    assert!(voter_actions::upvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(200),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::downvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    //
+    // Make the `ft_transfer` of OCT token fail
+    //
+    assert!(root
+        .call(oct_token.id(), "set_ft_transfer_is_rejected")
+        .args_json(json!({ "value": true }))
+        .gas(20_000_000_000_000)
+        .transact()
+        .await?
+        .is_success());
+    let result = voter_actions::withdraw_upvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(150),
+    )
+    .await?;
+    println!("Result of 'withdraw_upvote_deposit_of': {:?}", result);
+    let result = voter_actions::withdraw_downvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(100),
+    )
+    .await?;
+    println!("Result of 'withdraw_downvote_deposit_of': {:?}", result);
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.upvote_deposit.0, common::to_oct_amount(200));
+    assert_eq!(appchain.downvote_deposit.0, common::to_oct_amount(100));
+    assert_eq!(
+        registry_viewer::get_upvote_deposit_for(&registry, &appchain_id, &users[0])
+            .await?
+            .0,
+        common::to_oct_amount(200)
+    );
+    assert_eq!(
+        registry_viewer::get_downvote_deposit_for(&registry, &appchain_id, &users[0])
+            .await?
+            .0,
+        common::to_oct_amount(100)
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10 - 300)
+    );
+    //
+    // The appchain can NOT be removed while the withdrawals are in flight,
+    // and the deposits are restored when the withdrawals fail.
+    //
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let (withdraw_upvote_result, withdraw_downvote_result, remove_result) = tokio::join!(
+        voter_actions::withdraw_upvote_deposit_of(
+            &users[0],
+            &registry,
+            &appchain_id,
+            common::to_oct_amount(200),
+        ),
+        voter_actions::withdraw_downvote_deposit_of(
+            &users[0],
+            &registry,
+            &appchain_id,
+            common::to_oct_amount(100),
+        ),
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false),
+    );
+    println!(
+        "Results of concurrent withdrawals: {:?}, {:?}",
+        withdraw_upvote_result, withdraw_downvote_result
+    );
+    assert!(remove_result.unwrap().is_failure());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.appchain_state, AppchainState::Closed);
+    assert_eq!(appchain.upvote_deposit.0, common::to_oct_amount(200));
+    assert_eq!(appchain.downvote_deposit.0, common::to_oct_amount(100));
+    assert_eq!(
+        registry_viewer::get_voters_of(&registry, &appchain_id, 0, 10)
+            .await?
+            .len(),
+        1
+    );
+    //
+    // Make the `ft_transfer` of OCT token work again
+    //
+    assert!(root
+        .call(oct_token.id(), "set_ft_transfer_is_rejected")
+        .args_json(json!({ "value": false }))
+        .gas(20_000_000_000_000)
+        .transact()
+        .await?
+        .is_success());
+    assert!(voter_actions::withdraw_upvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(200),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::withdraw_downvote_deposit_of(
+        &users[0],
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.upvote_deposit.0, 0);
+    assert_eq!(appchain.downvote_deposit.0, 0);
+    assert_eq!(
+        common::get_ft_balance_of(&users[0], &oct_token).await?.0,
+        common::to_oct_amount(TOTAL_SUPPLY / 10)
+    );
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    //
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use crate::{
+    common,
+    contract_interfaces::{
+        appchain_lifecycle_manager, appchain_owner_actions, permissionless_actions,
+        registry_viewer, voter_actions,
+    },
+};
+use appchain_registry::types::{
+    AppchainRejectionCode, AppchainState, MultiTxsOperationProcessingResult, SubstrateTemplateType,
+};
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
+use near_sdk::{json_types::U128, AccountId};
+use std::{collections::HashMap, str::FromStr};
+
+const TOTAL_SUPPLY: u128 = 100_000_000;
+
+/// Test refunding vote deposits of a rejected appchain.
+#[tokio::test]
+async fn test_case6() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let total_supply = common::to_oct_amount(TOTAL_SUPPLY);
+    let (root, oct_token, registry, _council, users) =
+        common::basic_actions::initialize_contracts_and_users(&worker, total_supply, false).await?;
+    //
+    let appchain_id = "test_appchain1".to_string();
+    let amount = common::to_oct_amount(1000);
+    appchain_owner_actions::register_appchain(
+        &users[1],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        Some("appchain1 description".to_string()),
+        Some(SubstrateTemplateType::Barnacle),
+        Some("http://ddfs.dsdfs".to_string()),
+        Some("https://jldfs.yoasdfasd".to_string()),
+        Some("joe@lksdf.com".to_string()),
+        Some(AccountId::from_str(users[1].id().as_str()).unwrap()),
+        Some(U128::from(10000000)),
+        Some(U128::from(10000000)),
+        Some(U128::from(1000000)),
+        Some(U128::from(100)),
+        Some(FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "joeToken".to_string(),
+            symbol: "JOT".to_string(),
+            icon: Option::None,
+            reference: Option::None,
+            reference_hash: Option::None,
+            decimals: 18,
+        }),
+        Some(HashMap::from([("key1".to_string(), "value1".to_string())])),
+        amount,
+    )
+    .await
+    .expect("Failed in calling 'register_appchain'")
+    .unwrap();
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        None
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        appchain_lifecycle_manager::start_voting_appchain(&root, &registry, &appchain_id)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    //
+    for user in [&users[0], &users[2], &users[3]] {
+        assert!(voter_actions::upvote_appchain(
+            user,
+            &oct_token,
+            &registry,
+            &appchain_id,
+            common::to_oct_amount(200),
+        )
+        .await
+        .unwrap()
+        .is_success());
+    }
+    assert!(voter_actions::downvote_appchain(
+        &users[4],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(100),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(voter_actions::downvote_appchain(
+        &users[0],
+        &oct_token,
+        &registry,
+        &appchain_id,
+        common::to_oct_amount(50),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    //
+    assert!(
+        permissionless_actions::refund_votes_of(&users[0], &registry, &appchain_id, 2)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Closed);
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    //
+    let result = permissionless_actions::refund_votes_of(&users[0], &registry, &appchain_id, 2)
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        result.json::<MultiTxsOperationProcessingResult>()?,
+        MultiTxsOperationProcessingResult::NeedMoreGas
+    );
+    let voters = registry_viewer::get_voters_of(&registry, &appchain_id, 0, 10).await?;
+    assert_eq!(voters.len(), 2);
+    let result = permissionless_actions::refund_votes_of(&users[0], &registry, &appchain_id, 2)
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    assert_eq!(
+        result.json::<MultiTxsOperationProcessingResult>()?,
+        MultiTxsOperationProcessingResult::Ok
+    );
+    //
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.upvote_deposit.0, 0);
+    assert_eq!(appchain.downvote_deposit.0, 0);
+    for user in [&users[0], &users[2], &users[3], &users[4]] {
+        assert_eq!(
+            common::get_ft_balance_of(user, &oct_token).await?.0,
+            common::to_oct_amount(TOTAL_SUPPLY / 10)
+        );
+        assert_eq!(
+            registry_viewer::get_votes_of_account(&registry, user)
+                .await?
+                .len(),
+            0
+        );
+    }
+    assert_eq!(
+        common::get_ft_balance_of(&registry.as_account(), &oct_token)
+            .await?
+            .0,
+        common::to_oct_amount(1000)
+    );
+    //
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    Ok(())
+}
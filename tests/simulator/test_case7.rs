@@ -0,0 +1,317 @@
+use crate::{
+    common,
+    contract_interfaces::{
+        appchain_lifecycle_manager, appchain_owner_actions, registry_settings, registry_viewer,
+    },
+};
+use appchain_registry::types::{
+    AppchainRejectionCode, AppchainState, AppchainType, RegisterDepositAsset,
+    RegisterDepositRefundPolicy, SubstrateTemplateType,
+};
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
+use near_sdk::AccountId;
+use near_units::parse_near;
+use std::str::FromStr;
+
+const TOTAL_SUPPLY: u128 = 100_000_000;
+
+/// Test registering appchain with native NEAR token as register deposit.
+#[tokio::test]
+async fn test_case7() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let total_supply = common::to_oct_amount(TOTAL_SUPPLY);
+    let (root, _oct_token, registry, _council, users) =
+        common::basic_actions::initialize_contracts_and_users(&worker, total_supply, false).await?;
+    //
+    let appchain_id = "test_appchain1".to_string();
+    let fungible_token_metadata = FungibleTokenMetadata {
+        spec: FT_METADATA_SPEC.to_string(),
+        name: "joeToken".to_string(),
+        symbol: "JOT".to_string(),
+        icon: Option::None,
+        reference: Option::None,
+        reference_hash: Option::None,
+        decimals: 18,
+    };
+    let beneficiary = AccountId::from_str(users[1].id().as_str()).unwrap();
+    //
+    // NEAR is not accepted by default.
+    //
+    assert!(appchain_owner_actions::register_appchain_with_near(
+        &users[1],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(registry_settings::set_register_deposit_asset(
+        &users[1],
+        &registry,
+        &RegisterDepositAsset::Near,
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(registry_settings::set_register_deposit_asset(
+        &root,
+        &registry,
+        &RegisterDepositAsset::Near,
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let registry_settings = registry_viewer::get_registry_settings(&registry).await?;
+    assert_eq!(registry_settings.other_register_deposit_assets.len(), 1);
+    //
+    // Register with insufficient amount and then with more than the minimum amount.
+    // The surplus is returned to the registrant.
+    //
+    assert!(appchain_owner_actions::register_appchain_with_near(
+        &users[1],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("5 N"),
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(appchain_owner_actions::register_appchain_with_near(
+        &users[1],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("12 N"),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.register_deposit_asset, RegisterDepositAsset::Near);
+    assert_eq!(appchain.register_deposit.0, parse_near!("10 N"));
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(treasury.ledgers.len(), 1);
+    assert_eq!(treasury.ledgers[0].asset, RegisterDepositAsset::Near);
+    assert_eq!(
+        treasury.ledgers[0].pending_register_deposits.0,
+        parse_near!("10 N")
+    );
+    //
+    // Reject the appchain and claim the refund in NEAR.
+    //
+    assert!(registry_settings::change_register_deposit_refund_policy(
+        &root,
+        &registry,
+        &RegisterDepositRefundPolicy {
+            withdrawn_while_registered_percent: 0,
+            withdrawn_after_audit_percent: 0,
+            rejected_percent: 50,
+            went_live_percent: 0,
+        }
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let balance_before_claim = users[1].view_account().await?.balance;
+    assert!(appchain_owner_actions::claim_register_deposit_refund(
+        &users[1],
+        &registry,
+        &appchain_id
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(
+        appchain.refunded_register_deposit.unwrap().0,
+        parse_near!("5 N")
+    );
+    let balance_after_claim = users[1].view_account().await?.balance;
+    assert!(balance_after_claim > balance_before_claim + parse_near!("4.9 N"));
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(treasury.ledgers[0].pending_register_deposits.0, 0);
+    assert_eq!(treasury.ledgers[0].reserved_register_deposit_refunds.0, 0);
+    assert_eq!(treasury.ledgers[0].withdrawable_fees.0, parse_near!("5 N"));
+    //
+    // Reapply the closed appchain by its owner.
+    //
+    assert!(appchain_owner_actions::reapply_appchain_with_near(
+        &users[0],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    assert!(appchain_owner_actions::reapply_appchain_with_near(
+        &users[1],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_id).await?;
+    assert_eq!(appchain.appchain_state, AppchainState::Registered);
+    assert_eq!(
+        appchain.appchain_metadata.description,
+        "appchain description (reapplied)"
+    );
+    assert!(appchain.rejection.is_none());
+    assert!(appchain.refunded_register_deposit.is_none());
+    assert_eq!(appchain.previous_registrations.len(), 1);
+    assert_eq!(
+        appchain.previous_registrations[0]
+            .refunded_register_deposit
+            .unwrap()
+            .0,
+        parse_near!("5 N")
+    );
+    let history =
+        registry_viewer::get_appchain_state_history(&registry, &appchain_id, 0, 50).await?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].from, AppchainState::Closed);
+    assert_eq!(history[1].to, AppchainState::Registered);
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(
+        treasury.ledgers[0].pending_register_deposits.0,
+        parse_near!("10 N")
+    );
+    //
+    // Remove the appchain before its refund is claimed, the refund is kept for the owner.
+    //
+    assert!(appchain_lifecycle_manager::reject_appchain(
+        &root,
+        &registry,
+        &appchain_id,
+        AppchainRejectionCode::FailedAuditing,
+        "Failed in auditing again."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(
+        treasury.ledgers[0].reserved_register_deposit_refunds.0,
+        parse_near!("5 N")
+    );
+    assert_eq!(treasury.ledgers[0].withdrawable_fees.0, parse_near!("10 N"));
+    assert_eq!(
+        registry_viewer::get_claimable_register_deposit_of(
+            &registry,
+            &users[1],
+            &RegisterDepositAsset::Near
+        )
+        .await?
+        .0,
+        parse_near!("5 N")
+    );
+    assert!(
+        appchain_owner_actions::claim_register_deposit_refunds_of_removed_appchains(
+            &users[0],
+            &registry,
+            &RegisterDepositAsset::Near
+        )
+        .await
+        .unwrap()
+        .is_failure()
+    );
+    let balance_before_claim = users[1].view_account().await?.balance;
+    assert!(
+        appchain_owner_actions::claim_register_deposit_refunds_of_removed_appchains(
+            &users[1],
+            &registry,
+            &RegisterDepositAsset::Near
+        )
+        .await
+        .unwrap()
+        .is_success()
+    );
+    let balance_after_claim = users[1].view_account().await?.balance;
+    assert!(balance_after_claim > balance_before_claim + parse_near!("4.9 N"));
+    let treasury = registry_viewer::get_registry_treasury(&registry).await?;
+    assert_eq!(treasury.ledgers[0].reserved_register_deposit_refunds.0, 0);
+    assert_eq!(
+        registry_viewer::get_claimable_register_deposit_of(
+            &registry,
+            &users[1],
+            &RegisterDepositAsset::Near
+        )
+        .await?
+        .0,
+        0
+    );
+    //
+    // The id of the removed appchain can be registered again.
+    //
+    assert!(appchain_owner_actions::register_appchain_with_near(
+        &users[2],
+        &registry,
+        &appchain_id,
+        AppchainType::Substrate(SubstrateTemplateType::Barnacle),
+        beneficiary.clone(),
+        fungible_token_metadata.clone(),
+        parse_near!("10 N"),
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(
+        appchain_owner_actions::withdraw_appchain(&users[2], &registry, &appchain_id)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    assert!(
+        appchain_lifecycle_manager::remove_appchain(&root, &registry, &appchain_id, false)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let archived_appchains = registry_viewer::get_archived_appchains(&registry, 0, 50).await?;
+    assert_eq!(archived_appchains.len(), 2);
+    let archived_appchain = registry_viewer::get_archived_appchain(&registry, &appchain_id, 1)
+        .await?
+        .unwrap();
+    assert_eq!(archived_appchain.removal_index, 1);
+    assert_eq!(
+        archived_appchain.appchain_owner.as_str(),
+        users[2].id().as_str()
+    );
+    Ok(())
+}
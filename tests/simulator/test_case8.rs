@@ -0,0 +1,204 @@
+use crate::{
+    common,
+    contract_interfaces::{
+        appchain_lifecycle_manager, appchain_owner_actions, auditor_actions, registry_roles,
+        registry_settings, registry_viewer,
+    },
+};
+use appchain_registry::types::{
+    AppchainRejectionCode, AppchainState, AuditReport, SubstrateTemplateType,
+};
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
+use near_sdk::{json_types::U128, AccountId};
+use std::{collections::HashMap, str::FromStr};
+
+const TOTAL_SUPPLY: u128 = 100_000_000;
+
+/// Test auditing appchains by the quorum of auditors.
+#[tokio::test]
+async fn test_case8() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let total_supply = common::to_oct_amount(TOTAL_SUPPLY);
+    let (root, oct_token, registry, _council, users) =
+        common::basic_actions::initialize_contracts_and_users(&worker, total_supply, false).await?;
+    //
+    let appchain_ids = ["test_appchain1".to_string(), "test_appchain2".to_string()];
+    for (index, appchain_id) in appchain_ids.iter().enumerate() {
+        appchain_owner_actions::register_appchain(
+            &users[index],
+            &oct_token,
+            &registry,
+            appchain_id,
+            Some("appchain description".to_string()),
+            Some(SubstrateTemplateType::Barnacle),
+            Some("http://ddfs.dsdfs".to_string()),
+            Some("https://jldfs.yoasdfasd".to_string()),
+            Some("joe@lksdf.com".to_string()),
+            Some(AccountId::from_str(users[index].id().as_str()).unwrap()),
+            Some(U128::from(10000000)),
+            Some(U128::from(10000000)),
+            Some(U128::from(1000000)),
+            Some(U128::from(100)),
+            Some(FungibleTokenMetadata {
+                spec: FT_METADATA_SPEC.to_string(),
+                name: "joeToken".to_string(),
+                symbol: "JOT".to_string(),
+                icon: Option::None,
+                reference: Option::None,
+                reference_hash: Option::None,
+                decimals: 18,
+            }),
+            Some(HashMap::from([("key1".to_string(), "value1".to_string())])),
+            common::to_oct_amount(1000),
+        )
+        .await
+        .expect("Failed in calling 'register_appchain'")
+        .unwrap();
+    }
+    //
+    // Setup 3 auditors with threshold 2.
+    //
+    assert!(
+        registry_settings::change_audit_threshold(&root, &registry, 1)
+            .await
+            .unwrap()
+            .is_failure()
+    );
+    for auditor in &users[2..5] {
+        assert!(registry_roles::add_auditor(&root, &registry, auditor)
+            .await
+            .unwrap()
+            .is_success());
+    }
+    assert!(
+        registry_settings::change_audit_threshold(&root, &registry, 2)
+            .await
+            .unwrap()
+            .is_success()
+    );
+    let registry_roles = registry_viewer::get_registry_roles(&registry).await?;
+    assert_eq!(registry_roles.auditors.len(), 3);
+    //
+    // The lifecycle manager can not pass auditing directly.
+    //
+    assert!(appchain_lifecycle_manager::pass_auditing_appchain(
+        &root,
+        &registry,
+        &appchain_ids[0],
+        None
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    //
+    // Approved by 2 of 3 auditors.
+    //
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[0],
+        &registry,
+        &appchain_ids[0],
+        true,
+        None,
+        "Looks good."
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    let audit_report = AuditReport {
+        report_url: "https://example.com/audit_report.pdf".to_string(),
+        content_hash: "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+            .to_string(),
+        auditor: AccountId::from_str(users[2].id().as_str()).unwrap(),
+        notes: "No critical issue found.".to_string(),
+    };
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[2],
+        &registry,
+        &appchain_ids[0],
+        true,
+        Some(audit_report.clone()),
+        "Looks good."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[2],
+        &registry,
+        &appchain_ids[0],
+        true,
+        None,
+        "Looks good."
+    )
+    .await
+    .unwrap()
+    .is_failure());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_ids[0]).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Registered);
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[3],
+        &registry,
+        &appchain_ids[0],
+        true,
+        None,
+        "Agreed."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_ids[0]).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Audited);
+    assert_eq!(
+        appchain.audit_report.unwrap().report_url,
+        audit_report.report_url
+    );
+    let attestations =
+        registry_viewer::get_audit_attestations_of(&registry, &appchain_ids[0]).await?;
+    assert_eq!(attestations.len(), 2);
+    assert!(attestations.iter().all(|attestation| attestation.approved));
+    //
+    // Rejected by 2 of 3 auditors.
+    //
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[2],
+        &registry,
+        &appchain_ids[1],
+        false,
+        None,
+        "Missing function spec."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    assert!(auditor_actions::submit_audit_attestation(
+        &users[4],
+        &registry,
+        &appchain_ids[1],
+        false,
+        None,
+        "Missing function spec."
+    )
+    .await
+    .unwrap()
+    .is_success());
+    let appchain = registry_viewer::get_appchain_status_of(&registry, &appchain_ids[1]).await?;
+    assert_eq!(&appchain.appchain_state, &AppchainState::Closed);
+    assert_eq!(
+        appchain.rejection.unwrap().code,
+        AppchainRejectionCode::FailedAuditing
+    );
+    //
+    // The auditors can not be fewer than the threshold.
+    //
+    assert!(registry_roles::remove_auditor(&root, &registry, &users[4])
+        .await
+        .unwrap()
+        .is_success());
+    assert!(registry_roles::remove_auditor(&root, &registry, &users[3])
+        .await
+        .unwrap()
+        .is_failure());
+    let registry_roles = registry_viewer::get_registry_roles(&registry).await?;
+    assert_eq!(registry_roles.auditors.len(), 2);
+    Ok(())
+}